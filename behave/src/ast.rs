@@ -71,6 +71,9 @@ pub struct Variable {
 pub struct EnumVariant {
 	pub name: Ident,
 	pub value: Option<Expression>,
+	/// Struct-like payload fields for a tagged-union variant, or `None` for a plain
+	/// constant variant.
+	pub payload: Option<Vec<VarEntry>>,
 	pub range: Range<usize>,
 }
 
@@ -80,6 +83,13 @@ pub struct Enum {
 	pub variants: Vec<EnumVariant>,
 }
 
+impl Enum {
+	/// Looks up a variant by name.
+	pub fn variant(&self, name: &str) -> Option<&EnumVariant> {
+		self.variants.iter().find(|variant| variant.name.0 == name)
+	}
+}
+
 #[derive(Debug)]
 pub struct Struct {
 	pub name: Ident,
@@ -168,6 +178,7 @@ pub enum BinaryOperator {
 	Subtract,
 	Multiply,
 	Divide,
+	Modulo,
 	And,
 	Or,
 	Equal,
@@ -176,6 +187,9 @@ pub enum BinaryOperator {
 	Lesser,
 	GreaterThanOrEqual,
 	LesserThanOrEqual,
+	Pipe,
+	Range,
+	RangeInclusive,
 }
 
 #[derive(Debug)]
@@ -188,6 +202,8 @@ pub struct Index {
 pub struct Assignment {
 	pub variable: Box<Expression>,
 	pub value: Box<Expression>,
+	/// The operator of a compound assignment (`+=`, `-=`, …), or `None` for a plain `=`.
+	pub op: Option<BinaryOperator>,
 }
 
 #[derive(Debug)]
@@ -284,3 +300,719 @@ pub struct Path(pub Vec<Ident>, pub Range<usize>);
 
 #[derive(Debug)]
 pub struct Ident(pub String, pub Range<usize>);
+
+/// A read-only pre-order traversal of the AST. Override only the `visit_*` hooks a pass
+/// cares about — the defaults do nothing — and drive the walk with [`preorder`]. Every
+/// node is descended into regardless of which hooks are overridden, so lints (unused
+/// variables, unreachable `break`/`return`, empty `component` blocks), reference
+/// collection, and rename passes can all share one walker.
+pub trait Visitor {
+	fn visit_item(&mut self, item: &Item) { let _ = item; }
+	fn visit_stmt(&mut self, stmt: &Statement) { let _ = stmt; }
+	fn visit_expr(&mut self, expr: &Expression) { let _ = expr; }
+	fn visit_ident(&mut self, ident: &Ident) { let _ = ident; }
+}
+
+/// Walks `tree` in pre-order, visiting every item, statement, expression, and identifier.
+/// An expression statement holds no [`Expression`] node of its own: it reaches the visitor
+/// through `visit_stmt`, and only its subexpressions are passed to `visit_expr`.
+pub fn preorder(visitor: &mut impl Visitor, tree: &ASTTree) {
+	match tree {
+		ASTTree::Branch(map) => {
+			for child in map.values() {
+				preorder(visitor, child);
+			}
+		},
+		ASTTree::Leaf(ast) => walk_ast(visitor, ast),
+	}
+}
+
+fn walk_ast(visitor: &mut impl Visitor, ast: &AST) {
+	for import in ast.imports.iter() {
+		match &import.0 {
+			ImportType::Normal(path) => walk_path(visitor, path),
+			ImportType::Extern(expr) => walk_expr(visitor, expr),
+		}
+	}
+	match &ast.ast_data {
+		ASTType::Main(lods, behavior) => {
+			for lod in lods.0.iter() {
+				walk_expr(visitor, &lod.min_size);
+				walk_expr(visitor, &lod.file);
+			}
+			for stmt in behavior.0.iter() {
+				walk_stmt(visitor, stmt);
+			}
+		},
+		ASTType::Secondary(items) => {
+			for item in items.iter() {
+				walk_item(visitor, item);
+			}
+		},
+	}
+}
+
+fn walk_item(visitor: &mut impl Visitor, item: &Item) {
+	visitor.visit_item(item);
+	match &item.0 {
+		ItemType::Function(name, function) => {
+			visitor.visit_ident(name);
+			walk_function(visitor, function);
+		},
+		ItemType::Variable(var) => walk_variable(visitor, var),
+		ItemType::Template(template) => {
+			visitor.visit_ident(&template.name);
+			for arg in template.args.iter() {
+				walk_var_entry(visitor, arg);
+			}
+			for stmt in template.block.iter() {
+				walk_stmt(visitor, stmt);
+			}
+		},
+		ItemType::Struct(s) => {
+			visitor.visit_ident(&s.name);
+			for field in s.fields.iter() {
+				walk_var_entry(visitor, field);
+			}
+		},
+		ItemType::Enum(e) => {
+			visitor.visit_ident(&e.name);
+			for variant in e.variants.iter() {
+				visitor.visit_ident(&variant.name);
+				if let Some(value) = &variant.value {
+					walk_expr(visitor, value);
+				}
+				if let Some(payload) = &variant.payload {
+					for field in payload.iter() {
+						walk_var_entry(visitor, field);
+					}
+				}
+			}
+		},
+	}
+}
+
+fn walk_variable(visitor: &mut impl Visitor, var: &Variable) {
+	visitor.visit_ident(&var.name);
+	if let Some(ty) = &var.ty {
+		walk_type(visitor, ty);
+	}
+	if let Some(value) = &var.value {
+		walk_expr(visitor, value);
+	}
+}
+
+fn walk_function(visitor: &mut impl Visitor, function: &Function) {
+	for param in function.params.iter() {
+		walk_var_entry(visitor, param);
+	}
+	if let Some(ret) = &function.ret {
+		walk_type(visitor, ret);
+	}
+	walk_block(visitor, &function.block);
+}
+
+fn walk_var_entry(visitor: &mut impl Visitor, entry: &VarEntry) {
+	visitor.visit_ident(&entry.name);
+	walk_type(visitor, &entry.ty);
+	if let Some(default) = &entry.default {
+		walk_expr(visitor, default);
+	}
+}
+
+fn walk_type(visitor: &mut impl Visitor, ty: &Type) {
+	match &ty.0 {
+		TypeType::User(ident) => visitor.visit_ident(ident),
+		TypeType::Array(inner) | TypeType::Optional(inner) => walk_type(visitor, inner),
+		TypeType::Function(function) => {
+			for arg in function.args.iter() {
+				walk_type(visitor, arg);
+			}
+			if let Some(ret) = &function.ret {
+				walk_type(visitor, ret);
+			}
+		},
+		TypeType::Num | TypeType::Str | TypeType::Bool | TypeType::Code => {},
+	}
+}
+
+fn walk_stmt(visitor: &mut impl Visitor, stmt: &Statement) {
+	visitor.visit_stmt(stmt);
+	match &stmt.0 {
+		StatementType::Expression(expr) => walk_expression_type(visitor, expr),
+		StatementType::Declaration(var) => walk_variable(visitor, var),
+	}
+}
+
+fn walk_expr(visitor: &mut impl Visitor, expr: &Expression) {
+	visitor.visit_expr(expr);
+	walk_expression_type(visitor, &expr.0);
+}
+
+fn walk_expression_type(visitor: &mut impl Visitor, expr: &ExpressionType) {
+	match expr {
+		ExpressionType::None
+		| ExpressionType::String(_)
+		| ExpressionType::Number(_)
+		| ExpressionType::Boolean(_) => {},
+		ExpressionType::Block(block) | ExpressionType::Code(block) => walk_block(visitor, block),
+		ExpressionType::Function(function) => walk_function(visitor, function),
+		ExpressionType::Array(values) => {
+			for value in values.iter() {
+				walk_expr(visitor, value);
+			}
+		},
+		ExpressionType::Access(path) => walk_path(visitor, path),
+		ExpressionType::RPNAccess(inner) => walk_expr(visitor, inner),
+		ExpressionType::Index(index) => {
+			walk_expr(visitor, &index.array);
+			walk_expr(visitor, &index.index);
+		},
+		ExpressionType::Assignment(assignment) => {
+			walk_expr(visitor, &assignment.variable);
+			walk_expr(visitor, &assignment.value);
+		},
+		ExpressionType::Unary(_, inner) => walk_expr(visitor, inner),
+		ExpressionType::Binary(lhs, _, rhs) => {
+			walk_expr(visitor, lhs);
+			walk_expr(visitor, rhs);
+		},
+		ExpressionType::Call(call) => {
+			walk_expr(visitor, &call.callee);
+			for arg in call.args.iter() {
+				walk_expr(visitor, arg);
+			}
+		},
+		ExpressionType::IfChain(chain) => {
+			for (condition, block, _) in chain.ifs.iter() {
+				walk_expr(visitor, condition);
+				walk_block(visitor, block);
+			}
+			if let Some((block, _)) = &chain.else_part {
+				walk_block(visitor, block);
+			}
+		},
+		ExpressionType::Switch(switch) => {
+			walk_expr(visitor, &switch.on);
+			for case in switch.cases.iter() {
+				walk_expr(visitor, &case.value);
+				walk_expr(visitor, &case.code);
+			}
+		},
+		ExpressionType::While(while_loop) => {
+			walk_expr(visitor, &while_loop.condition);
+			walk_block(visitor, &while_loop.block);
+		},
+		ExpressionType::For(for_loop) => {
+			visitor.visit_ident(&for_loop.var);
+			walk_expr(visitor, &for_loop.container);
+			walk_block(visitor, &for_loop.block);
+		},
+		ExpressionType::Return(inner) | ExpressionType::Break(inner) => {
+			if let Some(inner) = inner {
+				walk_expr(visitor, inner);
+			}
+		},
+		ExpressionType::Use(us) => {
+			walk_path(visitor, &us.template);
+			for (name, expr) in us.args.iter() {
+				visitor.visit_ident(name);
+				walk_expr(visitor, expr);
+			}
+		},
+		ExpressionType::Component(component) => {
+			walk_expr(visitor, &component.name);
+			if let Some(node) = &component.node {
+				walk_expr(visitor, node);
+			}
+			for stmt in component.block.iter() {
+				walk_stmt(visitor, stmt);
+			}
+		},
+		ExpressionType::Animation(animation) => {
+			walk_expr(visitor, &animation.name);
+			walk_expr(visitor, &animation.length);
+			walk_expr(visitor, &animation.lag);
+			walk_expr(visitor, &animation.code);
+		},
+	}
+}
+
+fn walk_block(visitor: &mut impl Visitor, block: &Block) {
+	for stmt in block.statements.iter() {
+		walk_stmt(visitor, stmt);
+	}
+	if let Some(expr) = &block.expression {
+		walk_expr(visitor, expr);
+	}
+}
+
+fn walk_path(visitor: &mut impl Visitor, path: &Path) {
+	for ident in path.0.iter() {
+		visitor.visit_ident(ident);
+	}
+}
+
+/// The by-mutable-reference counterpart of [`Visitor`], for passes that rewrite the tree
+/// in place (e.g. rename refactors or span remapping). Driven by [`preorder_mut`].
+pub trait VisitorMut {
+	fn visit_item(&mut self, item: &mut Item) { let _ = item; }
+	fn visit_stmt(&mut self, stmt: &mut Statement) { let _ = stmt; }
+	fn visit_expr(&mut self, expr: &mut Expression) { let _ = expr; }
+	fn visit_ident(&mut self, ident: &mut Ident) { let _ = ident; }
+}
+
+/// Walks `tree` in pre-order, visiting every node by mutable reference.
+pub fn preorder_mut(visitor: &mut impl VisitorMut, tree: &mut ASTTree) {
+	match tree {
+		ASTTree::Branch(map) => {
+			for child in map.values_mut() {
+				preorder_mut(visitor, child);
+			}
+		},
+		ASTTree::Leaf(ast) => walk_ast_mut(visitor, ast),
+	}
+}
+
+fn walk_ast_mut(visitor: &mut impl VisitorMut, ast: &mut AST) {
+	for import in ast.imports.iter_mut() {
+		match &mut import.0 {
+			ImportType::Normal(path) => walk_path_mut(visitor, path),
+			ImportType::Extern(expr) => walk_expr_mut(visitor, expr),
+		}
+	}
+	match &mut ast.ast_data {
+		ASTType::Main(lods, behavior) => {
+			for lod in lods.0.iter_mut() {
+				walk_expr_mut(visitor, &mut lod.min_size);
+				walk_expr_mut(visitor, &mut lod.file);
+			}
+			for stmt in behavior.0.iter_mut() {
+				walk_stmt_mut(visitor, stmt);
+			}
+		},
+		ASTType::Secondary(items) => {
+			for item in items.iter_mut() {
+				walk_item_mut(visitor, item);
+			}
+		},
+	}
+}
+
+fn walk_item_mut(visitor: &mut impl VisitorMut, item: &mut Item) {
+	visitor.visit_item(item);
+	match &mut item.0 {
+		ItemType::Function(name, function) => {
+			visitor.visit_ident(name);
+			walk_function_mut(visitor, function);
+		},
+		ItemType::Variable(var) => walk_variable_mut(visitor, var),
+		ItemType::Template(template) => {
+			visitor.visit_ident(&mut template.name);
+			for arg in template.args.iter_mut() {
+				walk_var_entry_mut(visitor, arg);
+			}
+			for stmt in template.block.iter_mut() {
+				walk_stmt_mut(visitor, stmt);
+			}
+		},
+		ItemType::Struct(s) => {
+			visitor.visit_ident(&mut s.name);
+			for field in s.fields.iter_mut() {
+				walk_var_entry_mut(visitor, field);
+			}
+		},
+		ItemType::Enum(e) => {
+			visitor.visit_ident(&mut e.name);
+			for variant in e.variants.iter_mut() {
+				visitor.visit_ident(&mut variant.name);
+				if let Some(value) = &mut variant.value {
+					walk_expr_mut(visitor, value);
+				}
+				if let Some(payload) = &mut variant.payload {
+					for field in payload.iter_mut() {
+						walk_var_entry_mut(visitor, field);
+					}
+				}
+			}
+		},
+	}
+}
+
+fn walk_variable_mut(visitor: &mut impl VisitorMut, var: &mut Variable) {
+	visitor.visit_ident(&mut var.name);
+	if let Some(ty) = &mut var.ty {
+		walk_type_mut(visitor, ty);
+	}
+	if let Some(value) = &mut var.value {
+		walk_expr_mut(visitor, value);
+	}
+}
+
+fn walk_function_mut(visitor: &mut impl VisitorMut, function: &mut Function) {
+	for param in function.params.iter_mut() {
+		walk_var_entry_mut(visitor, param);
+	}
+	if let Some(ret) = &mut function.ret {
+		walk_type_mut(visitor, ret);
+	}
+	walk_block_mut(visitor, &mut function.block);
+}
+
+fn walk_var_entry_mut(visitor: &mut impl VisitorMut, entry: &mut VarEntry) {
+	visitor.visit_ident(&mut entry.name);
+	walk_type_mut(visitor, &mut entry.ty);
+	if let Some(default) = &mut entry.default {
+		walk_expr_mut(visitor, default);
+	}
+}
+
+fn walk_type_mut(visitor: &mut impl VisitorMut, ty: &mut Type) {
+	match &mut ty.0 {
+		TypeType::User(ident) => visitor.visit_ident(ident),
+		TypeType::Array(inner) | TypeType::Optional(inner) => walk_type_mut(visitor, inner),
+		TypeType::Function(function) => {
+			for arg in function.args.iter_mut() {
+				walk_type_mut(visitor, arg);
+			}
+			if let Some(ret) = &mut function.ret {
+				walk_type_mut(visitor, ret);
+			}
+		},
+		TypeType::Num | TypeType::Str | TypeType::Bool | TypeType::Code => {},
+	}
+}
+
+fn walk_stmt_mut(visitor: &mut impl VisitorMut, stmt: &mut Statement) {
+	visitor.visit_stmt(stmt);
+	match &mut stmt.0 {
+		StatementType::Expression(expr) => walk_expression_type_mut(visitor, expr),
+		StatementType::Declaration(var) => walk_variable_mut(visitor, var),
+	}
+}
+
+fn walk_expr_mut(visitor: &mut impl VisitorMut, expr: &mut Expression) {
+	visitor.visit_expr(expr);
+	walk_expression_type_mut(visitor, &mut expr.0);
+}
+
+fn walk_expression_type_mut(visitor: &mut impl VisitorMut, expr: &mut ExpressionType) {
+	match expr {
+		ExpressionType::None
+		| ExpressionType::String(_)
+		| ExpressionType::Number(_)
+		| ExpressionType::Boolean(_) => {},
+		ExpressionType::Block(block) | ExpressionType::Code(block) => walk_block_mut(visitor, block),
+		ExpressionType::Function(function) => walk_function_mut(visitor, function),
+		ExpressionType::Array(values) => {
+			for value in values.iter_mut() {
+				walk_expr_mut(visitor, value);
+			}
+		},
+		ExpressionType::Access(path) => walk_path_mut(visitor, path),
+		ExpressionType::RPNAccess(inner) => walk_expr_mut(visitor, inner),
+		ExpressionType::Index(index) => {
+			walk_expr_mut(visitor, &mut index.array);
+			walk_expr_mut(visitor, &mut index.index);
+		},
+		ExpressionType::Assignment(assignment) => {
+			walk_expr_mut(visitor, &mut assignment.variable);
+			walk_expr_mut(visitor, &mut assignment.value);
+		},
+		ExpressionType::Unary(_, inner) => walk_expr_mut(visitor, inner),
+		ExpressionType::Binary(lhs, _, rhs) => {
+			walk_expr_mut(visitor, lhs);
+			walk_expr_mut(visitor, rhs);
+		},
+		ExpressionType::Call(call) => {
+			walk_expr_mut(visitor, &mut call.callee);
+			for arg in call.args.iter_mut() {
+				walk_expr_mut(visitor, arg);
+			}
+		},
+		ExpressionType::IfChain(chain) => {
+			for (condition, block, _) in chain.ifs.iter_mut() {
+				walk_expr_mut(visitor, condition);
+				walk_block_mut(visitor, block);
+			}
+			if let Some((block, _)) = &mut chain.else_part {
+				walk_block_mut(visitor, block);
+			}
+		},
+		ExpressionType::Switch(switch) => {
+			walk_expr_mut(visitor, &mut switch.on);
+			for case in switch.cases.iter_mut() {
+				walk_expr_mut(visitor, &mut case.value);
+				walk_expr_mut(visitor, &mut case.code);
+			}
+		},
+		ExpressionType::While(while_loop) => {
+			walk_expr_mut(visitor, &mut while_loop.condition);
+			walk_block_mut(visitor, &mut while_loop.block);
+		},
+		ExpressionType::For(for_loop) => {
+			visitor.visit_ident(&mut for_loop.var);
+			walk_expr_mut(visitor, &mut for_loop.container);
+			walk_block_mut(visitor, &mut for_loop.block);
+		},
+		ExpressionType::Return(inner) | ExpressionType::Break(inner) => {
+			if let Some(inner) = inner {
+				walk_expr_mut(visitor, inner);
+			}
+		},
+		ExpressionType::Use(us) => {
+			walk_path_mut(visitor, &mut us.template);
+			for (name, expr) in us.args.iter_mut() {
+				visitor.visit_ident(name);
+				walk_expr_mut(visitor, expr);
+			}
+		},
+		ExpressionType::Component(component) => {
+			walk_expr_mut(visitor, &mut component.name);
+			if let Some(node) = &mut component.node {
+				walk_expr_mut(visitor, node);
+			}
+			for stmt in component.block.iter_mut() {
+				walk_stmt_mut(visitor, stmt);
+			}
+		},
+		ExpressionType::Animation(animation) => {
+			walk_expr_mut(visitor, &mut animation.name);
+			walk_expr_mut(visitor, &mut animation.length);
+			walk_expr_mut(visitor, &mut animation.lag);
+			walk_expr_mut(visitor, &mut animation.code);
+		},
+	}
+}
+
+fn walk_block_mut(visitor: &mut impl VisitorMut, block: &mut Block) {
+	for stmt in block.statements.iter_mut() {
+		walk_stmt_mut(visitor, stmt);
+	}
+	if let Some(expr) = &mut block.expression {
+		walk_expr_mut(visitor, expr);
+	}
+}
+
+fn walk_path_mut(visitor: &mut impl VisitorMut, path: &mut Path) {
+	for ident in path.0.iter_mut() {
+		visitor.visit_ident(ident);
+	}
+}
+
+/// Constructors for building AST nodes programmatically — used by template expansion and
+/// any codegen pass that would otherwise hand-assemble nested `Expression`/`Statement`
+/// structs. Every node produced here carries the [`synthetic`] span so generated nodes
+/// are distinguishable from parsed ones in diagnostics.
+pub mod make {
+	use std::ops::Range;
+
+	use super::*;
+
+	/// The span assigned to every synthesized node.
+	pub fn synthetic() -> Range<usize> { usize::MAX..usize::MAX }
+
+	fn expr(ty: ExpressionType) -> Expression { Expression(ty, synthetic()) }
+
+	pub fn ident(name: impl Into<String>) -> Ident { Ident(name.into(), synthetic()) }
+
+	pub fn path(segments: impl IntoIterator<Item = Ident>) -> Path {
+		Path(segments.into_iter().collect(), synthetic())
+	}
+
+	pub fn access(path: Path) -> Expression { expr(ExpressionType::Access(path)) }
+
+	pub fn number(value: f64) -> Expression { expr(ExpressionType::Number(value)) }
+
+	pub fn string(value: impl Into<String>) -> Expression { expr(ExpressionType::String(value.into())) }
+
+	pub fn boolean(value: bool) -> Expression { expr(ExpressionType::Boolean(value)) }
+
+	pub fn call(callee: Expression, args: Vec<Expression>) -> Expression {
+		expr(ExpressionType::Call(Call {
+			callee: Box::new(callee),
+			args,
+		}))
+	}
+
+	pub fn binary(lhs: Expression, op: BinaryOperator, rhs: Expression) -> Expression {
+		expr(ExpressionType::Binary(Box::new(lhs), op, Box::new(rhs)))
+	}
+
+	pub fn unary(op: UnaryOperator, operand: Expression) -> Expression {
+		expr(ExpressionType::Unary(op, Box::new(operand)))
+	}
+
+	pub fn block(statements: Vec<Statement>, tail: Option<Expression>) -> Block {
+		Block {
+			statements,
+			expression: tail.map(Box::new),
+		}
+	}
+
+	/// Wraps a [`Block`] as a block expression.
+	pub fn block_expr(block: Block) -> Expression { expr(ExpressionType::Block(block)) }
+
+	pub fn component(name: Expression, node: Option<Expression>, body: Vec<Statement>) -> Expression {
+		expr(ExpressionType::Component(Component {
+			name: Box::new(name),
+			node: node.map(Box::new),
+			block: body,
+		}))
+	}
+
+	/// Wraps an expression as an expression statement.
+	pub fn expr_stmt(expression: Expression) -> Statement {
+		Statement(StatementType::Expression(expression.0), synthetic())
+	}
+
+	pub fn declaration(name: Ident, ty: Option<Type>, value: Option<Expression>) -> Statement {
+		Statement(StatementType::Declaration(Variable { name, ty, value }), synthetic())
+	}
+
+	pub fn function(name: Ident, params: Vec<VarEntry>, ret: Option<Type>, block: Block) -> Item {
+		Item(
+			ItemType::Function(name, Function { params, ret, block }),
+			synthetic(),
+		)
+	}
+}
+
+/// A problem found while checking a `switch` against the enum it matches on.
+#[derive(Debug)]
+pub enum SwitchError<'a> {
+	/// A variant of the enum that the `switch` neither handles nor covers with a default.
+	MissingVariant(&'a str),
+	/// A case matching a variant an earlier case already matched.
+	DuplicateCase(&'a Ident),
+	/// A case naming something that is not a variant of the enum.
+	UnknownVariant(&'a Ident),
+}
+
+/// The variant identifier a `switch` case matches on, if its pattern is a simple variant
+/// path (`Variant` or `Enum::Variant`); anything else is treated as a default case.
+fn case_variant(case: &Case) -> Option<&Ident> {
+	match &case.value.0 {
+		ExpressionType::Access(path) => path.0.last(),
+		_ => None,
+	}
+}
+
+/// Checks a `switch` against `enum_def`, reporting unknown or duplicated cases and, when
+/// no default case is present, every variant left unhandled.
+pub fn check_switch<'a>(enum_def: &'a Enum, switch: &'a Switch) -> Vec<SwitchError<'a>> {
+	let mut errors = Vec::new();
+	let mut seen: Vec<&str> = Vec::new();
+	let mut has_default = false;
+
+	for case in switch.cases.iter() {
+		match case_variant(case) {
+			Some(ident) => {
+				if enum_def.variant(&ident.0).is_none() {
+					errors.push(SwitchError::UnknownVariant(ident));
+				} else if seen.contains(&ident.0.as_str()) {
+					errors.push(SwitchError::DuplicateCase(ident));
+				} else {
+					seen.push(&ident.0);
+				}
+			},
+			None => has_default = true,
+		}
+	}
+
+	if !has_default {
+		for variant in enum_def.variants.iter() {
+			if !seen.contains(&variant.name.0.as_str()) {
+				errors.push(SwitchError::MissingVariant(&variant.name.0));
+			}
+		}
+	}
+
+	errors
+}
+
+/// Reports variant names that clash with a user type already in scope, per the rule that
+/// variant names enter the type namespace.
+pub fn variant_type_clashes<'a>(enum_def: &'a Enum, is_type_in_scope: impl Fn(&str) -> bool) -> Vec<&'a Ident> {
+	enum_def
+		.variants
+		.iter()
+		.filter(|variant| is_type_in_scope(&variant.name.0))
+		.map(|variant| &variant.name)
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn make_nodes_carry_synthetic_span() {
+		let Expression(_, range) = make::number(1.0);
+		assert_eq!(range, make::synthetic());
+	}
+
+	#[test]
+	fn make_builds_a_nested_call() {
+		let call = make::call(
+			make::access(make::path([make::ident("f")])),
+			vec![make::number(1.0), make::string("x")],
+		);
+		match call.0 {
+			ExpressionType::Call(Call { callee, args }) => {
+				assert!(matches!(callee.0, ExpressionType::Access(_)));
+				assert_eq!(args.len(), 2);
+				assert!(matches!(args[0].0, ExpressionType::Number(n) if n == 1.0));
+				assert!(matches!(args[1].0, ExpressionType::String(ref s) if s == "x"));
+			},
+			_ => panic!("expected a call expression"),
+		}
+	}
+
+	#[test]
+	fn make_block_keeps_statements_and_tail() {
+		let sum = make::binary(make::number(1.0), BinaryOperator::Add, make::number(2.0));
+		assert!(matches!(sum.0, ExpressionType::Binary(..)));
+		let block = make::block(vec![make::expr_stmt(sum)], Some(make::number(3.0)));
+		assert_eq!(block.statements.len(), 1);
+		assert!(block.expression.is_some());
+	}
+
+	#[test]
+	fn preorder_descends_into_every_node() {
+		#[derive(Default)]
+		struct Counter {
+			items: usize,
+			idents: usize,
+			exprs: usize,
+		}
+
+		impl Visitor for Counter {
+			fn visit_item(&mut self, _: &Item) { self.items += 1; }
+			fn visit_ident(&mut self, _: &Ident) { self.idents += 1; }
+			fn visit_expr(&mut self, _: &Expression) { self.exprs += 1; }
+		}
+
+		// A one-item module holding `fn f() { 1 + 2 }`.
+		let body = make::block(
+			vec![],
+			Some(make::binary(make::number(1.0), BinaryOperator::Add, make::number(2.0))),
+		);
+		let item = make::function(make::ident("f"), vec![], None, body);
+		let mut tree = ASTTree::new();
+		tree.add_ast(
+			&["m".to_string()],
+			AST {
+				imports: vec![],
+				ast_data: ASTType::Secondary(vec![item]),
+			},
+		);
+
+		let mut counter = Counter::default();
+		preorder(&mut counter, &tree);
+
+		assert_eq!(counter.items, 1);
+		assert_eq!(counter.idents, 1); // the function name `f`
+		assert_eq!(counter.exprs, 3); // the `+` and its two operands
+	}
+}
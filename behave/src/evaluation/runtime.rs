@@ -14,6 +14,7 @@ use crate::ast::{
 	Call,
 	Component,
 	EnumAccess,
+	EnumType,
 	Expression,
 	ExpressionType,
 	For,
@@ -30,10 +31,12 @@ use crate::ast::{
 	StatementType,
 	StructCreate,
 	Switch,
+	SwitchError,
 	TypeType,
 	UnaryOperator,
 	Use,
 	While,
+	check_switch,
 };
 use crate::diagnostic::{Diagnostic, Label, Level};
 use crate::evaluation::rpn::RPNCompiler;
@@ -54,6 +57,7 @@ pub enum Flow<'a> {
 	Ok(Value<'a>),
 	Return(Location<'a>, Value<'a>),
 	Break(Location<'a>, Option<Value<'a>>),
+	Continue(Location<'a>),
 	Err(Vec<Diagnostic>),
 }
 
@@ -87,6 +91,162 @@ impl<'a> FromResidual<Result<Infallible, Vec<Diagnostic>>> for Flow<'a> {
 	fn from_residual(residual: Result<Infallible, Vec<Diagnostic>>) -> Self { Flow::Err(residual.unwrap_err()) }
 }
 
+/// A map key restricted to the hashable subset of [`Value`]. Maps key on strings,
+/// numbers, booleans, and enum variants; every other value type is rejected when the
+/// map is built so a key can never become unfindable. Numbers are stored as normalised
+/// bit patterns (with `-0.0` folded to `0.0`); `NaN` is refused outright.
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum MapKey {
+	String(String),
+	Number(u64),
+	Boolean(bool),
+	Enum(EnumAccess),
+}
+
+/// Why a [`Value`] cannot be used as a map key.
+enum MapKeyError {
+	Nan,
+	NotHashable,
+}
+
+impl MapKey {
+	fn from_value(value: &Value) -> Result<MapKey, MapKeyError> {
+		match value {
+			Value::String(s) => Ok(MapKey::String(s.clone())),
+			Value::Number(n) if n.is_nan() => Err(MapKeyError::Nan),
+			Value::Number(n) => Ok(MapKey::Number((if *n == 0.0 { 0.0 } else { *n }).to_bits())),
+			Value::Boolean(b) => Ok(MapKey::Boolean(*b)),
+			Value::Enum(e) => Ok(MapKey::Enum(e.clone())),
+			_ => Err(MapKeyError::NotHashable),
+		}
+	}
+}
+
+/// Insertion-order-preserving, hash-indexed backing store for [`Value::Map`]. The entry
+/// vector keeps iteration deterministic for animation and template output, while the
+/// index turns lookups into O(1) by key.
+#[derive(Clone)]
+pub struct ValueMap<'a> {
+	entries: Vec<(Value<'a>, Value<'a>)>,
+	index: HashMap<MapKey, usize>,
+}
+
+impl<'a> ValueMap<'a> {
+	fn new() -> Self {
+		Self {
+			entries: Vec::new(),
+			index: HashMap::new(),
+		}
+	}
+
+	/// Appends an entry, returning `false` without modifying the map if `key` is already
+	/// present. The caller has already validated that `key` is hashable.
+	fn insert(&mut self, key: MapKey, k: Value<'a>, v: Value<'a>) -> bool {
+		if self.index.contains_key(&key) {
+			return false;
+		}
+		self.index.insert(key, self.entries.len());
+		self.entries.push((k, v));
+		true
+	}
+
+	fn get(&self, key: &MapKey) -> Option<&Value<'a>> { self.index.get(key).map(|&i| &self.entries[i].1) }
+
+	fn into_entries(self) -> Vec<(Value<'a>, Value<'a>)> { self.entries }
+}
+
+/// A uniform iterator over the iterable [`Value`] kinds, used by `for` loops. Ranges
+/// generate their numbers lazily rather than materialising a vector; strings yield
+/// single-character strings; maps yield `[key, value]` pairs.
+enum CIterator<'a> {
+	Array(std::vec::IntoIter<Value<'a>>),
+	Map {
+		iter: std::vec::IntoIter<(Value<'a>, Value<'a>)>,
+		key_ty: RuntimeType,
+		value_ty: RuntimeType,
+	},
+	String(std::vec::IntoIter<char>),
+	Range { current: f64, end: f64, inclusive: bool },
+}
+
+impl<'a> CIterator<'a> {
+	/// Builds an iterator for a loop source, or `None` if the value cannot be iterated.
+	fn from_value(value: Value<'a>) -> Option<Self> {
+		match value {
+			Value::Array(_, values) => Some(CIterator::Array(values.into_iter())),
+			Value::Map(key_ty, value_ty, map) => Some(CIterator::Map {
+				iter: map.into_entries().into_iter(),
+				key_ty,
+				value_ty,
+			}),
+			Value::String(s) => Some(CIterator::String(s.chars().collect::<Vec<_>>().into_iter())),
+			Value::Range(start, end, inclusive) => Some(CIterator::Range {
+				current: start,
+				end,
+				inclusive,
+			}),
+			_ => None,
+		}
+	}
+}
+
+impl<'a> Iterator for CIterator<'a> {
+	type Item = Value<'a>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		match self {
+			CIterator::Array(iter) => iter.next(),
+			// Each entry is surfaced as a two-element `[key, value]` pair. Its element type is the key
+			// type when the key and value share one, and otherwise their sum — so `get_type` and the
+			// assignment type checks see a type both elements genuinely satisfy, rather than the `None`
+			// sentinel or a single element's type that the other element would violate.
+			CIterator::Map { iter, key_ty, value_ty } => iter.next().map(|(key, value)| {
+				let element = if key_ty == value_ty {
+					key_ty.clone()
+				} else {
+					RuntimeType::Sum(vec![key_ty.clone(), value_ty.clone()])
+				};
+				Value::Array(element, vec![key, value])
+			}),
+			CIterator::String(iter) => iter.next().map(|c| Value::String(c.to_string())),
+			CIterator::Range {
+				current,
+				end,
+				inclusive,
+			} => {
+				let done = if *inclusive { *current > *end } else { *current >= *end };
+				if done {
+					None
+				} else {
+					let value = *current;
+					*current += 1.0;
+					Some(Value::Number(value))
+				}
+			},
+		}
+	}
+}
+
+/// Renders a [`SwitchError`] from [`check_switch`] as a diagnostic. A missing variant is
+/// anchored to the scrutinee since it has no case of its own; the others point at the
+/// offending case.
+fn switch_diagnostic(error: &SwitchError, switch: &Switch) -> Diagnostic {
+	match error {
+		SwitchError::MissingVariant(name) => {
+			Diagnostic::new(Level::Error, format!("non-exhaustive switch: variant `{}` is not handled", name))
+				.add_label(Label::primary("add a case for it or a default case", switch.on.1.clone()))
+		},
+		SwitchError::DuplicateCase(ident) => {
+			Diagnostic::new(Level::Error, format!("duplicate case for variant `{}`", ident.0))
+				.add_label(Label::primary("variant already matched by an earlier case", ident.1.clone()))
+		},
+		SwitchError::UnknownVariant(ident) => {
+			Diagnostic::new(Level::Error, format!("`{}` is not a variant of this enum", ident.0))
+				.add_label(Label::primary("unknown variant", ident.1.clone()))
+		},
+	}
+}
+
 #[derive(Clone, Copy)]
 struct ContextualInfo {
 	is_in_component: bool,
@@ -113,6 +273,8 @@ macro_rules! evaluate {
 				.add_label(Label::primary("return expression here `{}`", loc))]),
 			Flow::Break(loc, _) => Err(vec![Diagnostic::new(Level::Error, "unexpected break")
 				.add_label(Label::primary("break expression here `{}`", loc))]),
+			Flow::Continue(loc) => Err(vec![Diagnostic::new(Level::Error, "unexpected continue")
+				.add_label(Label::primary("continue expression here `{}`", loc))]),
 			Flow::Err(err) => Err(err),
 		}
 	};
@@ -143,6 +305,13 @@ macro_rules! evaluate {
 				);
 				Default::default()
 			},
+			Flow::Continue(loc) => {
+				$errors.push(
+					Diagnostic::new(Level::Error, "unexpected continue")
+						.add_label(Label::primary("continue expression here `{}`", loc)),
+				);
+				Default::default()
+			},
 			Flow::Err(err) => {
 				$errors.extend(err);
 				Default::default()
@@ -151,6 +320,44 @@ macro_rules! evaluate {
 	};
 }
 
+/// Reports whether `src` is an incomplete REPL entry that should keep reading
+/// continuation lines: an unterminated string, unbalanced `()`/`[]`/`{}`, or a trailing
+/// binary operator awaiting its right-hand side.
+pub fn needs_more_input(src: &str) -> bool {
+	let mut depth: i32 = 0;
+	let mut in_string = false;
+	let mut escaped = false;
+	for c in src.chars() {
+		if in_string {
+			if escaped {
+				escaped = false;
+			} else if c == '\\' {
+				escaped = true;
+			} else if c == '"' {
+				in_string = false;
+			}
+			continue;
+		}
+
+		match c {
+			'"' => in_string = true,
+			'(' | '[' | '{' => depth += 1,
+			')' | ']' | '}' => depth -= 1,
+			_ => {},
+		}
+	}
+
+	if in_string || depth > 0 {
+		return true;
+	}
+
+	const TRAILING_OPERATORS: &[&str] = &[
+		"|>", "&&", "||", "==", "!=", "<=", ">=", "..=", "..", "+", "-", "*", "/", "%", "<", ">", "=",
+	];
+	let trimmed = src.trim_end();
+	TRAILING_OPERATORS.iter().any(|op| trimmed.ends_with(op))
+}
+
 impl<'a> ExpressionEvaluator<'a> {
 	pub fn new(item_map: &'a ItemMap<'a>) -> Self {
 		Self {
@@ -184,6 +391,51 @@ impl<'a> ExpressionEvaluator<'a> {
 				.add_label(Label::primary("return expression here `{}`", loc))]),
 			Flow::Break(loc, _) => Err(vec![Diagnostic::new(Level::Error, "unexpected break")
 				.add_label(Label::primary("break expression here `{}`", loc))]),
+			Flow::Continue(loc) => Err(vec![Diagnostic::new(Level::Error, "unexpected continue")
+				.add_label(Label::primary("continue expression here `{}`", loc))]),
+			Flow::Err(err) => Err(err),
+		}
+	}
+
+	/// Creates an evaluator for interactive (REPL) use. The outermost scope is pushed once
+	/// here and never torn down, so top-level declarations entered with
+	/// [`eval_incremental`](Self::eval_incremental) stay visible to subsequent inputs.
+	pub fn repl(item_map: &'a ItemMap<'a>) -> Self {
+		let mut evaluator = Self::new(item_map);
+		evaluator.stack.scope();
+		evaluator
+	}
+
+	/// Evaluates a single statement against the persistent REPL scope, returning its
+	/// value. Expressions evaluate to their result; a declaration binds a new variable
+	/// (evaluating to `None`) that remains live for later calls.
+	pub fn eval_incremental(&mut self, stmt: &Statement<'a>) -> Result<Value<'a>, Vec<Diagnostic>> {
+		match &stmt.0 {
+			StatementType::Declaration(var) => {
+				let value = match &var.value {
+					Some(expr) => self.evaluate_to_value(expr)?,
+					None => Value::None,
+				};
+				self.stack.new_var(&var.name, value);
+				Ok(Value::None)
+			},
+			StatementType::Expression(expr) => {
+				self.evaluate_to_value(&Expression(expr.clone(), stmt.1.clone()))
+			},
+		}
+	}
+
+	/// Evaluates an expression to a plain value, turning control-flow escapes that are
+	/// meaningless at the top level (`return`, `break`, `continue`) into diagnostics.
+	fn evaluate_to_value(&mut self, expr: &Expression<'a>) -> Result<Value<'a>, Vec<Diagnostic>> {
+		match self.evaluate_expression(expr) {
+			Flow::Ok(value) => Ok(value),
+			Flow::Return(loc, _) => Err(vec![Diagnostic::new(Level::Error, "unexpected return")
+				.add_label(Label::primary("return expression here `{}`", loc))]),
+			Flow::Break(loc, _) => Err(vec![Diagnostic::new(Level::Error, "unexpected break")
+				.add_label(Label::primary("break expression here `{}`", loc))]),
+			Flow::Continue(loc) => Err(vec![Diagnostic::new(Level::Error, "unexpected continue")
+				.add_label(Label::primary("continue expression here `{}`", loc))]),
 			Flow::Err(err) => Err(err),
 		}
 	}
@@ -205,7 +457,7 @@ impl<'a> ExpressionEvaluator<'a> {
 			Assignment(assignment) => self.evaluate_assignment(assignment)?,
 			Unary(op, expr) => self.evaluate_unary(*op, expr)?,
 			Binary(left, op, right) => self.evaluate_binary(*op, left, right)?,
-			Call(call) => self.evaluate_call(call)?,
+			Call(call) => self.evaluate_call(call, None)?,
 			IfChain(chain) => self.evaluate_if(chain)?,
 			Switch(switch) => self.evaluate_switch(switch)?,
 			While(whil) => self.evaluate_while(whil)?,
@@ -213,6 +465,7 @@ impl<'a> ExpressionEvaluator<'a> {
 			StructCreate(s) => self.evaluate_struct(s)?,
 			Return(e) => self.evaluate_return(e.as_deref(), expr.1.clone())?,
 			Break(e) => self.evaluate_break(e.as_deref(), expr.1.clone())?,
+			Continue => self.evaluate_continue(expr.1.clone())?,
 			Behavior(expr) => {
 				use BehaviorExpression::*;
 				match expr {
@@ -350,6 +603,12 @@ impl<'a> ExpressionEvaluator<'a> {
 					},
 				},
 				ResolvedAccess::Local => {
+					let value = if let Some(op) = assignment.op {
+						let current = Self::value(&mut self.stack, &self.item_map, &access.path)?.clone();
+						self.apply_binary(op, current, value, &access.path.1, &assignment.value.1)?
+					} else {
+						value
+					};
 					let val = Self::value(&mut self.stack, &self.item_map, &access.path)?;
 					let var_ty = val.get_type(&self.item_map);
 					let val_ty = value.get_type(&self.item_map);
@@ -382,6 +641,46 @@ impl<'a> ExpressionEvaluator<'a> {
 				},
 				ResolvedAccess::Local => {
 					let idx = self.evaluate_expression(index.as_ref())?;
+					let value = if let Some(op) = assignment.op {
+						let current = {
+							let val = Self::value(&mut self.stack, &self.item_map, &access.path)?;
+							if let Value::Array(_, array) = val {
+								if let Value::Number(idx) = idx {
+									let len = array.len();
+									if let Some(element) = array.into_iter().nth(idx as usize) {
+										element.clone()
+									} else {
+										return Flow::Err(vec![Diagnostic::new(
+											Level::Error,
+											"array index out of bounds",
+										)
+										.add_label(Label::primary(
+											format!("array length is {}, but index was {}", len, idx as usize),
+											index.1.clone(),
+										))]);
+									}
+								} else {
+									return Flow::Err(vec![Diagnostic::new(
+										Level::Error,
+										"array index must be a number",
+									)
+									.add_label(Label::primary(
+										format!("expression result is of type `{}`", idx.get_type(self.item_map)),
+										index.1.clone(),
+									))]);
+								}
+							} else {
+								return Flow::Err(vec![Diagnostic::new(Level::Error, "can only index arrays")
+									.add_label(Label::primary(
+										format!("expression result is of type `{}`", val.get_type(self.item_map)),
+										access.path.1.clone(),
+									))]);
+							}
+						};
+						self.apply_binary(op, current, value, &access.path.1, &assignment.value.1)?
+					} else {
+						value
+					};
 					let val = Self::value(&mut self.stack, &self.item_map, &access.path)?;
 					if let Value::Array(ty, array) = val {
 						if let Value::Number(idx) = idx {
@@ -461,6 +760,13 @@ impl<'a> ExpressionEvaluator<'a> {
 								);
 								continue;
 							},
+							Flow::Continue(loc) => {
+								errors.push(
+									Diagnostic::new(Level::Error, "unexpected continue")
+										.add_label(Label::primary("continue expression here `{}`", loc)),
+								);
+								continue;
+							},
 							Flow::Err(err) => {
 								errors.extend(err);
 								continue;
@@ -565,6 +871,8 @@ impl<'a> ExpressionEvaluator<'a> {
 		)
 	}
 
+	fn evaluate_continue(&mut self, loc: Location<'a>) -> Flow<'a> { Flow::Continue(loc) }
+
 	fn evaluate_array(&mut self, values: &[Expression<'a>]) -> Flow<'a> {
 		let mut errors = Vec::new();
 		let (mut ty, mut ty_loc) = (RuntimeType::None, None);
@@ -593,6 +901,13 @@ impl<'a> ExpressionEvaluator<'a> {
 					);
 					None
 				},
+				Flow::Continue(loc) => {
+					errors.push(
+						Diagnostic::new(Level::Error, "unexpected continue")
+							.add_label(Label::primary("continue expression here `{}`", loc)),
+					);
+					None
+				},
 				Flow::Err(vec) => {
 					errors.extend(vec);
 					None
@@ -659,6 +974,13 @@ impl<'a> ExpressionEvaluator<'a> {
 					);
 					None
 				},
+				(Flow::Continue(loc), _) | (_, Flow::Continue(loc)) => {
+					errors.push(
+						Diagnostic::new(Level::Error, "unexpected continue")
+							.add_label(Label::primary("continue expression here `{}`", loc)),
+					);
+					None
+				},
 				(Flow::Err(vec), _) | (_, Flow::Err(vec)) => {
 					errors.extend(vec);
 					None
@@ -698,8 +1020,38 @@ impl<'a> ExpressionEvaluator<'a> {
 			}
 		}
 
+		let mut value_map = ValueMap::new();
+		for t in map {
+			let key = match MapKey::from_value(&t.1) {
+				Ok(key) => key,
+				Err(MapKeyError::Nan) => {
+					errors.push(
+						Diagnostic::new(Level::Error, "`NaN` cannot be used as a map key")
+							.add_label(Label::primary("this key evaluates to `NaN`", t.0)),
+					);
+					continue;
+				},
+				Err(MapKeyError::NotHashable) => {
+					errors.push(Diagnostic::new(Level::Error, "map key type is not hashable").add_label(
+						Label::primary(
+							format!("keys of type `{}` cannot be hashed", t.1.get_type(self.item_map)),
+							t.0,
+						),
+					));
+					continue;
+				},
+			};
+
+			if !value_map.insert(key, t.1, t.3) {
+				errors.push(
+					Diagnostic::new(Level::Error, "duplicate map key")
+						.add_label(Label::primary("this key is already present in the map", t.0)),
+				);
+			}
+		}
+
 		if errors.len() == 0 {
-			Flow::Ok(Value::Map(k_ty, v_ty, map.into_iter().map(|i| (i.1, i.3)).collect()))
+			Flow::Ok(Value::Map(k_ty, v_ty, value_map))
 		} else {
 			Flow::Err(errors)
 		}
@@ -733,14 +1085,15 @@ impl<'a> ExpressionEvaluator<'a> {
 				let idx = self.evaluate_expression(&index.index)?;
 				let idx_ty = idx.get_type(&self.item_map);
 				if idx_ty == key {
-					for pair in map {
-						if pair.0 == idx {
-							return Flow::Ok(pair.1);
-						}
+					match MapKey::from_value(&idx) {
+						Ok(key) => match map.get(&key) {
+							Some(value) => Flow::Ok(value.clone()),
+							None => Flow::Err(vec![Diagnostic::new(Level::Error, "key does not exist in map")
+								.add_label(Label::primary("key does not exist", index.index.1.clone()))]),
+						},
+						Err(_) => Flow::Err(vec![Diagnostic::new(Level::Error, "`NaN` cannot index a map")
+							.add_label(Label::primary("this key evaluates to `NaN`", index.index.1.clone()))]),
 					}
-
-					Flow::Err(vec![Diagnostic::new(Level::Error, "key does not exist in map")
-						.add_label(Label::primary("key does not exist", index.index.1.clone()))])
 				} else {
 					Flow::Err(vec![Diagnostic::new(Level::Error, "incorrect map index type")
 						.add_label(Label::primary(
@@ -788,201 +1141,278 @@ impl<'a> ExpressionEvaluator<'a> {
 	}
 
 	fn evaluate_binary(&mut self, operator: BinaryOperator, left: &Expression<'a>, right: &Expression<'a>) -> Flow<'a> {
+		if let BinaryOperator::Pipe = operator {
+			return self.evaluate_pipe(left, right);
+		}
 		let lhs = self.evaluate_expression(left)?;
 		let rhs = self.evaluate_expression(right)?;
+		Flow::Ok(self.apply_binary(operator, lhs, rhs, &left.1, &right.1)?)
+	}
+
+	/// Evaluates `left |> right(args...)` by threading the left-hand result in as the
+	/// first argument of the call on the right. The right expression must be a call.
+	fn evaluate_pipe(&mut self, left: &Expression<'a>, right: &Expression<'a>) -> Flow<'a> {
+		if let ExpressionType::Call(call) = &right.0 {
+			let value = self.evaluate_expression(left)?;
+			self.evaluate_call(call, Some((value, left.1.clone())))
+		} else {
+			Flow::Err(vec![Diagnostic::new(Level::Error, "right of `|>` must be a function call")
+				.add_label(Label::primary(
+					"expected a function call here",
+					right.1.clone(),
+				))])
+		}
+	}
+
+	/// Combines two already-evaluated values with a binary operator. Shared by plain
+	/// binary expressions and compound assignment so both obey the same numeric and
+	/// string semantics. `left`/`right` locate the operands for diagnostics.
+	fn apply_binary(
+		&self, operator: BinaryOperator, lhs: Value<'a>, rhs: Value<'a>, left: &Location<'a>, right: &Location<'a>,
+	) -> Result<Value<'a>, Vec<Diagnostic>> {
 		match operator {
 			BinaryOperator::Add => match (lhs, rhs) {
-				(Value::String(lhs), Value::String(rhs)) => Flow::Ok(Value::String(lhs + &rhs)),
-				(Value::Number(lhs), Value::Number(rhs)) => Flow::Ok(Value::Number(lhs + rhs)),
-				(lhs, rhs) => Flow::Err(vec![Diagnostic::new(Level::Error, "cannot add")
+				(Value::String(lhs), Value::String(rhs)) => Ok(Value::String(lhs + &rhs)),
+				(Value::Number(lhs), Value::Number(rhs)) => Ok(Value::Number(lhs + rhs)),
+				(lhs, rhs) => Err(vec![Diagnostic::new(Level::Error, "cannot add")
 					.add_label(Label::primary(
 						format!("expression result is of type `{}`", lhs.get_type(self.item_map)),
-						left.1.clone(),
+						left.clone(),
 					))
 					.add_label(Label::primary(
 						format!("expression result is of type `{}`", rhs.get_type(self.item_map)),
-						right.1.clone(),
+						right.clone(),
 					))]),
 			},
 			BinaryOperator::Subtract => match (lhs, rhs) {
-				(Value::Number(lhs), Value::Number(rhs)) => Flow::Ok(Value::Number(lhs - rhs)),
-				(lhs, rhs) => Flow::Err(vec![Diagnostic::new(Level::Error, "cannot subtract")
+				(Value::Number(lhs), Value::Number(rhs)) => Ok(Value::Number(lhs - rhs)),
+				(lhs, rhs) => Err(vec![Diagnostic::new(Level::Error, "cannot subtract")
 					.add_label(Label::primary(
 						format!("expression result is of type `{}`", lhs.get_type(self.item_map)),
-						left.1.clone(),
+						left.clone(),
 					))
 					.add_label(Label::primary(
 						format!("expression result is of type `{}`", rhs.get_type(self.item_map)),
-						right.1.clone(),
+						right.clone(),
 					))]),
 			},
 			BinaryOperator::Multiply => match (lhs, rhs) {
-				(Value::Number(lhs), Value::Number(rhs)) => Flow::Ok(Value::Number(lhs * rhs)),
-				(lhs, rhs) => Flow::Err(vec![Diagnostic::new(Level::Error, "cannot multiply")
+				(Value::Number(lhs), Value::Number(rhs)) => Ok(Value::Number(lhs * rhs)),
+				(lhs, rhs) => Err(vec![Diagnostic::new(Level::Error, "cannot multiply")
 					.add_label(Label::primary(
 						format!("expression result is of type `{}`", lhs.get_type(self.item_map)),
-						left.1.clone(),
+						left.clone(),
 					))
 					.add_label(Label::primary(
 						format!("expression result is of type `{}`", rhs.get_type(self.item_map)),
-						right.1.clone(),
+						right.clone(),
 					))]),
 			},
 			BinaryOperator::Divide => match (lhs, rhs) {
-				(Value::Number(lhs), Value::Number(rhs)) => Flow::Ok(Value::Number(lhs / rhs)),
-				(lhs, rhs) => Flow::Err(vec![Diagnostic::new(Level::Error, "cannot divide")
+				(Value::Number(lhs), Value::Number(rhs)) => Ok(Value::Number(lhs / rhs)),
+				(lhs, rhs) => Err(vec![Diagnostic::new(Level::Error, "cannot divide")
 					.add_label(Label::primary(
 						format!("expression result is of type `{}`", lhs.get_type(self.item_map)),
-						left.1.clone(),
+						left.clone(),
 					))
 					.add_label(Label::primary(
 						format!("expression result is of type `{}`", rhs.get_type(self.item_map)),
-						right.1.clone(),
+						right.clone(),
+					))]),
+			},
+			BinaryOperator::Modulo => match (lhs, rhs) {
+				(Value::Number(lhs), Value::Number(rhs)) => Ok(Value::Number(lhs % rhs)),
+				(lhs, rhs) => Err(vec![Diagnostic::new(Level::Error, "cannot take remainder")
+					.add_label(Label::primary(
+						format!("expression result is of type `{}`", lhs.get_type(self.item_map)),
+						left.clone(),
+					))
+					.add_label(Label::primary(
+						format!("expression result is of type `{}`", rhs.get_type(self.item_map)),
+						right.clone(),
 					))]),
 			},
 			BinaryOperator::And => match (lhs, rhs) {
-				(Value::Boolean(lhs), Value::Boolean(rhs)) => Flow::Ok(Value::Boolean(lhs && rhs)),
-				(lhs, rhs) => Flow::Err(vec![Diagnostic::new(Level::Error, "cannot and")
+				(Value::Boolean(lhs), Value::Boolean(rhs)) => Ok(Value::Boolean(lhs && rhs)),
+				(lhs, rhs) => Err(vec![Diagnostic::new(Level::Error, "cannot and")
 					.add_label(Label::primary(
 						format!("expression result is of type `{}`", lhs.get_type(self.item_map)),
-						left.1.clone(),
+						left.clone(),
 					))
 					.add_label(Label::primary(
 						format!("expression result is of type `{}`", rhs.get_type(self.item_map)),
-						right.1.clone(),
+						right.clone(),
 					))]),
 			},
 			BinaryOperator::Or => match (lhs, rhs) {
-				(Value::Boolean(lhs), Value::Boolean(rhs)) => Flow::Ok(Value::Boolean(lhs || rhs)),
-				(lhs, rhs) => Flow::Err(vec![Diagnostic::new(Level::Error, "cannot or")
+				(Value::Boolean(lhs), Value::Boolean(rhs)) => Ok(Value::Boolean(lhs || rhs)),
+				(lhs, rhs) => Err(vec![Diagnostic::new(Level::Error, "cannot or")
 					.add_label(Label::primary(
 						format!("expression result is of type `{}`", lhs.get_type(self.item_map)),
-						left.1.clone(),
+						left.clone(),
 					))
 					.add_label(Label::primary(
 						format!("expression result is of type `{}`", rhs.get_type(self.item_map)),
-						right.1.clone(),
+						right.clone(),
 					))]),
 			},
 			BinaryOperator::Equal => match (lhs, rhs) {
-				(Value::Boolean(lhs), Value::Boolean(rhs)) => Flow::Ok(Value::Boolean(lhs == rhs)),
-				(Value::String(lhs), Value::String(rhs)) => Flow::Ok(Value::Boolean(lhs == rhs)),
-				(Value::Number(lhs), Value::Number(rhs)) => Flow::Ok(Value::Boolean(lhs == rhs)),
-				(Value::Array(_, lhs), Value::Array(_, rhs)) => Flow::Ok(Value::Boolean(lhs == rhs)),
-				(Value::None, Value::None) => Flow::Ok(Value::Boolean(true)),
+				(Value::Boolean(lhs), Value::Boolean(rhs)) => Ok(Value::Boolean(lhs == rhs)),
+				(Value::String(lhs), Value::String(rhs)) => Ok(Value::Boolean(lhs == rhs)),
+				(Value::Number(lhs), Value::Number(rhs)) => Ok(Value::Boolean(lhs == rhs)),
+				(Value::Array(_, lhs), Value::Array(_, rhs)) => Ok(Value::Boolean(lhs == rhs)),
+				(Value::None, Value::None) => Ok(Value::Boolean(true)),
 				(
 					Value::Enum(EnumAccess { id: l_id, value: lhs }),
 					Value::Enum(EnumAccess { id: r_id, value: rhs }),
-				) if l_id == r_id => Flow::Ok(Value::Boolean(lhs == rhs)),
-				(lhs, rhs) => Flow::Err(vec![Diagnostic::new(Level::Error, "cannot equate")
+				) if l_id == r_id => Ok(Value::Boolean(lhs == rhs)),
+				(lhs, rhs) => Err(vec![Diagnostic::new(Level::Error, "cannot equate")
 					.add_label(Label::primary(
 						format!("expression result is of type `{}`", lhs.get_type(self.item_map)),
-						left.1.clone(),
+						left.clone(),
 					))
 					.add_label(Label::primary(
 						format!("expression result is of type `{}`", rhs.get_type(self.item_map)),
-						right.1.clone(),
+						right.clone(),
 					))]),
 			},
 			BinaryOperator::NotEqual => match (lhs, rhs) {
-				(Value::Boolean(lhs), Value::Boolean(rhs)) => Flow::Ok(Value::Boolean(lhs != rhs)),
-				(Value::String(lhs), Value::String(rhs)) => Flow::Ok(Value::Boolean(lhs != rhs)),
-				(Value::Number(lhs), Value::Number(rhs)) => Flow::Ok(Value::Boolean(lhs != rhs)),
-				(Value::Array(_, lhs), Value::Array(_, rhs)) => Flow::Ok(Value::Boolean(lhs != rhs)),
-				(Value::None, Value::None) => Flow::Ok(Value::Boolean(false)),
+				(Value::Boolean(lhs), Value::Boolean(rhs)) => Ok(Value::Boolean(lhs != rhs)),
+				(Value::String(lhs), Value::String(rhs)) => Ok(Value::Boolean(lhs != rhs)),
+				(Value::Number(lhs), Value::Number(rhs)) => Ok(Value::Boolean(lhs != rhs)),
+				(Value::Array(_, lhs), Value::Array(_, rhs)) => Ok(Value::Boolean(lhs != rhs)),
+				(Value::None, Value::None) => Ok(Value::Boolean(false)),
 				(
 					Value::Enum(EnumAccess { id: l_id, value: lhs }),
 					Value::Enum(EnumAccess { id: r_id, value: rhs }),
-				) if l_id == r_id => Flow::Ok(Value::Boolean(lhs != rhs)),
-				(lhs, rhs) => Flow::Err(vec![Diagnostic::new(Level::Error, "cannot compare")
+				) if l_id == r_id => Ok(Value::Boolean(lhs != rhs)),
+				(lhs, rhs) => Err(vec![Diagnostic::new(Level::Error, "cannot compare")
 					.add_label(Label::primary(
 						format!("expression result is of type `{}`", lhs.get_type(self.item_map)),
-						left.1.clone(),
+						left.clone(),
 					))
 					.add_label(Label::primary(
 						format!("expression result is of type `{}`", rhs.get_type(self.item_map)),
-						right.1.clone(),
+						right.clone(),
 					))]),
 			},
 			BinaryOperator::Greater => match (lhs, rhs) {
-				(Value::Number(lhs), Value::Number(rhs)) => Flow::Ok(Value::Boolean(lhs > rhs)),
-				(lhs, rhs) => Flow::Err(vec![Diagnostic::new(Level::Error, "cannot compare")
+				(Value::Number(lhs), Value::Number(rhs)) => Ok(Value::Boolean(lhs > rhs)),
+				(lhs, rhs) => Err(vec![Diagnostic::new(Level::Error, "cannot compare")
 					.add_label(Label::primary(
 						format!("expression result is of type `{}`", lhs.get_type(self.item_map)),
-						left.1.clone(),
+						left.clone(),
 					))
 					.add_label(Label::primary(
 						format!("expression result is of type `{}`", rhs.get_type(self.item_map)),
-						right.1.clone(),
+						right.clone(),
 					))]),
 			},
 			BinaryOperator::Lesser => match (lhs, rhs) {
-				(Value::Number(lhs), Value::Number(rhs)) => Flow::Ok(Value::Boolean(lhs < rhs)),
-				(lhs, rhs) => Flow::Err(vec![Diagnostic::new(Level::Error, "cannot compare")
+				(Value::Number(lhs), Value::Number(rhs)) => Ok(Value::Boolean(lhs < rhs)),
+				(lhs, rhs) => Err(vec![Diagnostic::new(Level::Error, "cannot compare")
 					.add_label(Label::primary(
 						format!("expression result is of type `{}`", lhs.get_type(self.item_map)),
-						left.1.clone(),
+						left.clone(),
 					))
 					.add_label(Label::primary(
 						format!("expression result is of type `{}`", rhs.get_type(self.item_map)),
-						right.1.clone(),
+						right.clone(),
 					))]),
 			},
 			BinaryOperator::GreaterThanOrEqual => match (lhs, rhs) {
-				(Value::Number(lhs), Value::Number(rhs)) => Flow::Ok(Value::Boolean(lhs >= rhs)),
-				(lhs, rhs) => Flow::Err(vec![Diagnostic::new(Level::Error, "cannot compare")
+				(Value::Number(lhs), Value::Number(rhs)) => Ok(Value::Boolean(lhs >= rhs)),
+				(lhs, rhs) => Err(vec![Diagnostic::new(Level::Error, "cannot compare")
 					.add_label(Label::primary(
 						format!("expression result is of type `{}`", lhs.get_type(self.item_map)),
-						left.1.clone(),
+						left.clone(),
 					))
 					.add_label(Label::primary(
 						format!("expression result is of type `{}`", rhs.get_type(self.item_map)),
-						right.1.clone(),
+						right.clone(),
 					))]),
 			},
 			BinaryOperator::LesserThanOrEqual => match (lhs, rhs) {
-				(Value::Number(lhs), Value::Number(rhs)) => Flow::Ok(Value::Boolean(lhs <= rhs)),
-				(lhs, rhs) => Flow::Err(vec![Diagnostic::new(Level::Error, "cannot compare")
+				(Value::Number(lhs), Value::Number(rhs)) => Ok(Value::Boolean(lhs <= rhs)),
+				(lhs, rhs) => Err(vec![Diagnostic::new(Level::Error, "cannot compare")
+					.add_label(Label::primary(
+						format!("expression result is of type `{}`", lhs.get_type(self.item_map)),
+						left.clone(),
+					))
+					.add_label(Label::primary(
+						format!("expression result is of type `{}`", rhs.get_type(self.item_map)),
+						right.clone(),
+					))]),
+			},
+			BinaryOperator::Range => match (lhs, rhs) {
+				(Value::Number(lhs), Value::Number(rhs)) => Ok(Value::Range(lhs, rhs, false)),
+				(lhs, rhs) => Err(vec![Diagnostic::new(Level::Error, "cannot build range")
 					.add_label(Label::primary(
 						format!("expression result is of type `{}`", lhs.get_type(self.item_map)),
-						left.1.clone(),
+						left.clone(),
 					))
 					.add_label(Label::primary(
 						format!("expression result is of type `{}`", rhs.get_type(self.item_map)),
-						right.1.clone(),
+						right.clone(),
 					))]),
 			},
+			BinaryOperator::RangeInclusive => match (lhs, rhs) {
+				(Value::Number(lhs), Value::Number(rhs)) => Ok(Value::Range(lhs, rhs, true)),
+				(lhs, rhs) => Err(vec![Diagnostic::new(Level::Error, "cannot build range")
+					.add_label(Label::primary(
+						format!("expression result is of type `{}`", lhs.get_type(self.item_map)),
+						left.clone(),
+					))
+					.add_label(Label::primary(
+						format!("expression result is of type `{}`", rhs.get_type(self.item_map)),
+						right.clone(),
+					))]),
+			},
+			BinaryOperator::Pipe => unreachable!("`|>` is handled by evaluate_pipe before apply_binary"),
 		}
 	}
 
-	fn evaluate_call(&mut self, call: &Call<'a>) -> Flow<'a> {
+	fn evaluate_call(&mut self, call: &Call<'a>, prepend: Option<(Value<'a>, Location<'a>)>) -> Flow<'a> {
 		let mut errors = Vec::new();
 		let callee = self.evaluate_expression(&call.callee)?;
-		let args = call
-			.args
-			.iter()
-			.filter_map(|expr| match self.evaluate_expression(expr) {
-				Flow::Ok(val) => Some(val),
+
+		// When called through the `|>` operator the piped value is spliced in ahead of the
+		// written arguments, so its location travels alongside it for diagnostics.
+		let mut args = Vec::new();
+		let mut arg_locs = Vec::new();
+		if let Some((value, loc)) = &prepend {
+			args.push(value.clone());
+			arg_locs.push(loc.clone());
+		}
+		for expr in call.args.iter() {
+			match self.evaluate_expression(expr) {
+				Flow::Ok(val) => {
+					args.push(val);
+					arg_locs.push(expr.1.clone());
+				},
 				Flow::Return(loc, _) => {
 					errors.push(
 						Diagnostic::new(Level::Error, "unexpected return")
 							.add_label(Label::primary("return expression here `{}`", loc)),
 					);
-					None
 				},
 				Flow::Break(loc, _) => {
 					errors.push(
 						Diagnostic::new(Level::Error, "unexpected break")
 							.add_label(Label::primary("break expression here `{}`", loc)),
 					);
-					None
+				},
+				Flow::Continue(loc) => {
+					errors.push(
+						Diagnostic::new(Level::Error, "unexpected continue")
+							.add_label(Label::primary("continue expression here `{}`", loc)),
+					);
 				},
 				Flow::Err(err) => {
 					errors.extend(err);
-					None
 				},
-			})
-			.collect::<Vec<_>>();
+			}
+		}
 
 		if let Value::Function(f) = callee {
 			match f {
@@ -999,7 +1429,7 @@ impl<'a> ExpressionEvaluator<'a> {
 										Diagnostic::new(Level::Error, "mismatched argument types")
 											.add_label(Label::primary(
 												format!("this expression result is of type `{}`...", arg_pair.1 .1),
-												call.args[arg_pair.0].1.clone(),
+												arg_locs[arg_pair.0].clone(),
 											))
 											.add_label(Label::secondary(
 												format!("...but type `{}` is expected", arg_pair.1 .0),
@@ -1037,6 +1467,13 @@ impl<'a> ExpressionEvaluator<'a> {
 									);
 									return Flow::Err(errors);
 								},
+								Flow::Continue(loc) => {
+									errors.push(
+										Diagnostic::new(Level::Error, "unexpected continue")
+											.add_label(Label::primary("continue expression here `{}`", loc)),
+									);
+									return Flow::Err(errors);
+								},
 								Flow::Return(loc, ret) => (loc, ret),
 								Flow::Err(err) => {
 									errors.extend(err);
@@ -1080,7 +1517,7 @@ impl<'a> ExpressionEvaluator<'a> {
 					}
 				},
 				FunctionValue::Inbuilt(inbuilt) => {
-					self.evaluate_inbuilt_function(inbuilt, call.callee.1.clone(), &call.args)
+					self.evaluate_inbuilt_function(inbuilt, call.callee.1.clone(), &call.args, prepend.map(|p| p.0))
 				},
 			}
 		} else {
@@ -1095,10 +1532,10 @@ impl<'a> ExpressionEvaluator<'a> {
 	}
 
 	fn evaluate_inbuilt_function(
-		&mut self, func: InbuiltFunction, loc: Location<'a>, args: &[Expression<'a>],
+		&mut self, func: InbuiltFunction, loc: Location<'a>, args: &[Expression<'a>], prepend: Option<Value<'a>>,
 	) -> Flow<'a> {
 		match func {
-			InbuiltFunction::Format => self.evaluate_format(loc, args),
+			InbuiltFunction::Format => self.evaluate_format(loc, args, prepend),
 		}
 	}
 
@@ -1126,6 +1563,13 @@ impl<'a> ExpressionEvaluator<'a> {
 						);
 						continue;
 					},
+					Flow::Continue(loc) => {
+						errors.push(
+							Diagnostic::new(Level::Error, "unexpected continue")
+								.add_label(Label::primary("continue expression here `{}`", loc)),
+						);
+						continue;
+					},
 					Flow::Err(err) => {
 						errors.extend(err);
 						continue;
@@ -1405,80 +1849,189 @@ impl<'a> ExpressionEvaluator<'a> {
 
 	fn evaluate_switch(&mut self, switch: &Switch<'a>) -> Flow<'a> {
 		let on = self.evaluate_expression(&switch.on)?;
+
+		// When matching on a user enum, check the cases against the enum definition before
+		// running any arm: a variant left unhandled with no default case, a duplicated case,
+		// or a case naming a non-variant is an error rather than a silent fall-through.
+		if let Value::Enum(EnumAccess { id: EnumType::User(e), .. }) = &on {
+			let errors = check_switch(self.item_map.get_enum(*e), switch);
+			if !errors.is_empty() {
+				return Flow::Err(errors.iter().map(|error| switch_diagnostic(error, switch)).collect());
+			}
+		}
+
+		// A case whose pattern is not a variant path is the switch's default arm (see
+		// `check_switch`); on an enum it matches whatever is left once the variant cases miss.
+		let on_enum = matches!(on, Value::Enum(EnumAccess { id: EnumType::User(_), .. }));
+
+		let mut default = Option::None;
+		let mut chosen = Option::None;
 		for case in switch.cases.iter() {
-			if on == self.evaluate_expression(&case.value)? {
-				return self.evaluate_expression(&case.code);
+			if on_enum && !matches!(case.value.0, ExpressionType::Access(_)) {
+				default = default.or(Some(case));
+			} else if on == self.evaluate_expression(&case.value)? {
+				chosen = Some(case);
+				break;
 			}
 		}
 
+		if let Some(case) = chosen.or(default) {
+			// Run the matched arm in its own scope so a variant's payload fields are bound
+			// only for the body of the case they belong to.
+			self.stack.scope();
+			self.bind_case_payload(&on, &case.value);
+			let flow = self.evaluate_expression(&case.code);
+			self.stack.end_scope();
+			return flow;
+		}
+
 		Flow::Ok(Value::None)
 	}
 
-	fn evaluate_while(&mut self, while_loop: &While<'a>) -> Flow<'a> { todo!("For not implemented") }
-
-	fn evaluate_for(&mut self, for_loop: &For<'a>) -> Flow<'a> { todo!("While not implemented") }
-
-	fn evaluate_format(&mut self, loc: Location<'a>, args: &[Expression<'a>]) -> Flow<'a> {
-		if let Some(arg) = args.get(0) {
-			let value = self.evaluate_expression(arg)?;
-			if let Value::String(mut s) = value {
-				let format_replacement = s.matches("{}");
-				let arity = format_replacement.count();
-				if arity == args.len() - 1 {
-					let mut errors = Vec::new();
-					// I hate strings, please don't sue me.
-					for expr in args[1..].iter() {
-						let value = self.evaluate_expression(expr)?;
-						let replace = match value {
-							Value::String(s) => s,
-							Value::Number(n) => n.to_string(),
-							Value::Boolean(b) => b.to_string(),
-							_ => {
-								errors.push(
-									Diagnostic::new(Level::Error, "can only format primitive types").add_label(
-										Label::primary(
-											format!(
-												"this expression has a result of type `{}`",
-												value.get_type(&self.item_map)
-											),
-											expr.1.clone(),
+	/// Binds the payload fields declared by the variant a case matched as locals in the
+	/// current scope, so the arm body can refer to them by name. The enum definition is the
+	/// source of the field names; a matched enum value does not carry payload field values of
+	/// its own yet, so the fields start out as [`Value::None`].
+	fn bind_case_payload(&mut self, on: &Value<'a>, pattern: &Expression<'a>) {
+		if let (Value::Enum(EnumAccess { id: EnumType::User(e), .. }), ExpressionType::Access(path)) =
+			(on, &pattern.0)
+		{
+			if let Some(name) = path.0.last() {
+				if let Some(variant) = self.item_map.get_enum(*e).variant(&name.0) {
+					if let Some(payload) = &variant.payload {
+						for field in payload.iter() {
+							self.stack.new_var(&field.name, Value::None);
+						}
+					}
+				}
+			}
+		}
+	}
+
+	fn evaluate_while(&mut self, while_loop: &While<'a>) -> Flow<'a> {
+		loop {
+			let cond = self.evaluate_expression(&while_loop.condition)?;
+			let cond = if let Value::Boolean(cond) = cond {
+				cond
+			} else {
+				return Flow::Err(vec![Diagnostic::new(Level::Error, "while condition must be a boolean")
+					.add_label(Label::primary(
+						format!("expression result is of type `{}`", cond.get_type(self.item_map)),
+						while_loop.condition.1.clone(),
+					))]);
+			};
+
+			if !cond {
+				break;
+			}
+
+			match self.evaluate_block(&while_loop.block) {
+				Flow::Ok(_) | Flow::Continue(_) => {},
+				Flow::Break(_, _) => break,
+				flow @ (Flow::Return(..) | Flow::Err(_)) => return flow,
+			}
+		}
+
+		Flow::Ok(Value::None)
+	}
+
+	fn evaluate_for(&mut self, for_loop: &For<'a>) -> Flow<'a> {
+		let source = self.evaluate_expression(&for_loop.container)?;
+		let ty = source.get_type(self.item_map);
+		let iter = match CIterator::from_value(source) {
+			Some(iter) => iter,
+			None => {
+				return Flow::Err(vec![Diagnostic::new(Level::Error, "value is not iterable")
+					.add_label(Label::primary(
+						format!("expression result is of type `{}`", ty),
+						for_loop.container.1.clone(),
+					))]);
+			},
+		};
+
+		for item in iter {
+			self.stack.scope();
+			self.stack.new_var(&for_loop.var, item);
+			let flow = self.evaluate_block(&for_loop.block);
+			self.stack.end_scope();
+
+			match flow {
+				Flow::Ok(_) | Flow::Continue(_) => {},
+				Flow::Break(_, _) => break,
+				flow @ (Flow::Return(..) | Flow::Err(_)) => return flow,
+			}
+		}
+
+		Flow::Ok(Value::None)
+	}
+
+	fn evaluate_format(&mut self, loc: Location<'a>, args: &[Expression<'a>], prepend: Option<Value<'a>>) -> Flow<'a> {
+		// When piped into with `|>` the format string is the piped value; otherwise it is
+		// the first written argument and the remaining arguments fill the `{}` holes.
+		let (format, fmt_loc, fmt_args) = match prepend {
+			Some(value) => (value, loc.clone(), args),
+			None => match args.split_first() {
+				Some((arg, rest)) => (self.evaluate_expression(arg)?, arg.1.clone(), rest),
+				None => {
+					return Flow::Err(vec![Diagnostic::new(Level::Error, "missing format string")
+						.add_label(Label::primary("in this invocation of `format`", loc))]);
+				},
+			},
+		};
+
+		if let Value::String(mut s) = format {
+			let arity = s.matches("{}").count();
+			if arity == fmt_args.len() {
+				let mut errors = Vec::new();
+				// I hate strings, please don't sue me.
+				for expr in fmt_args.iter() {
+					let value = self.evaluate_expression(expr)?;
+					let replace = match value {
+						Value::String(s) => s,
+						Value::Number(n) => n.to_string(),
+						Value::Boolean(b) => b.to_string(),
+						_ => {
+							errors.push(
+								Diagnostic::new(Level::Error, "can only format primitive types").add_label(
+									Label::primary(
+										format!(
+											"this expression has a result of type `{}`",
+											value.get_type(&self.item_map)
 										),
+										expr.1.clone(),
 									),
-								);
-								continue;
-							},
-						};
-						s = s.replacen("{}", &replace, 1);
-					}
+								),
+							);
+							continue;
+						},
+					};
+					s = s.replacen("{}", &replace, 1);
+				}
 
-					if errors.len() == 0 {
-						Flow::Ok(Value::String(s))
-					} else {
-						Flow::Err(errors)
-					}
+				if errors.len() == 0 {
+					Flow::Ok(Value::String(s))
 				} else {
-					Flow::Err(vec![Diagnostic::new(
-						Level::Error,
-						"incorrect number of format arguments",
-					)
-					.add_label(Label::primary(
-						format!("expected {} arguments, found {}", arity, args.len() - 1),
-						loc,
-					))])
+					Flow::Err(errors)
 				}
 			} else {
 				Flow::Err(vec![Diagnostic::new(
 					Level::Error,
-					"format string must be of type `str`",
+					"incorrect number of format arguments",
 				)
 				.add_label(Label::primary(
-					format!("expression has a result of type `{}`", value.get_type(&self.item_map)),
+					format!("expected {} arguments, found {}", arity, fmt_args.len()),
 					loc,
 				))])
 			}
 		} else {
-			Flow::Err(vec![Diagnostic::new(Level::Error, "missing format string")
-				.add_label(Label::primary("in this invocation of `format`", loc))])
+			Flow::Err(vec![Diagnostic::new(
+				Level::Error,
+				"format string must be of type `str`",
+			)
+			.add_label(Label::primary(
+				format!("expression has a result of type `{}`", format.get_type(&self.item_map)),
+				fmt_loc,
+			))])
 		}
 	}
 
@@ -1,128 +1,198 @@
+use std::io::{self, Write};
+use std::ops::Range;
 use std::str::Chars;
 
 use uuid::Uuid;
 
-pub struct XMLWriter {
-	data: String,
+pub struct XMLWriter<W: Write> {
+	sink: Counting<W>,
 	indent: usize,
 	element_stack: Vec<String>,
+	source_map: Vec<SourceMapEntry>,
 }
 
-impl XMLWriter {
-	pub fn start() -> Self {
-		Self {
-			data: format!(
-				r#"<?xml version="1.0" encoding="utf-8"?>
+/// Links one emitted XML element back to the behave source it was generated from.
+pub struct SourceMapEntry {
+	/// Byte offset of the element's opening `<` in the generated XML document.
+	pub output_offset: usize,
+	/// Range in the original `.beh` source that produced the element.
+	pub source: Range<usize>,
+}
+
+/// Companion artifact produced alongside the generated XML. The simulator reports
+/// behavior errors against byte offsets in the XML; pairing those offsets with the
+/// entries here recovers the originating source range in the `.beh` file.
+pub struct SourceMap {
+	pub entries: Vec<SourceMapEntry>,
+}
 
-<!-- 
+impl<W: Write> XMLWriter<W> {
+	pub fn start(sink: W) -> io::Result<Self> {
+		let mut sink = Counting { inner: sink, written: 0 };
+		write!(
+			sink,
+			r#"<?xml version="1.0" encoding="utf-8"?>
+
+<!--
 	This XML file was generated by the behave compiler.
-			
+
 	Manual changes to this file may cause unexpected behavior.
 	Manual changes will be lost if the behave project is recompiled.
 -->
-			
+
 <ModelInfo version="1.0" guid="{{{}}}">
 "#,
-				Uuid::new_v4().to_hyphenated()
-			),
+			Uuid::new_v4().to_hyphenated()
+		)?;
+
+		Ok(Self {
+			sink,
 			indent: 1,
 			element_stack: Vec::new(),
-		}
+			source_map: Vec::new(),
+		})
 	}
 
-	pub fn start_element(&mut self, name: impl AsRef<str>) {
-		self.indent();
-		self.data.push('<');
-		self.element_stack
-			.push(String::from_iter(EscapeIterator::new(name.as_ref())));
-		self.data.extend(EscapeIterator::new(name.as_ref()));
-		self.data += ">\n";
+	pub fn start_element(&mut self, name: impl AsRef<str>) -> io::Result<()> {
+		self.indent()?;
+		let name = String::from_iter(EscapeIterator::new(name.as_ref()));
+		writeln!(self.sink, "<{}>", name)?;
+		self.element_stack.push(name);
+
+		self.indent += 1;
+		Ok(())
+	}
+
+	/// Like [`start_element`](Self::start_element), but also records the byte offset of
+	/// the emitted element and the `src` range it originated from into the source map.
+	pub fn start_element_mapped(&mut self, name: impl AsRef<str>, src: Range<usize>) -> io::Result<()> {
+		self.indent()?;
+		self.source_map.push(SourceMapEntry {
+			output_offset: self.sink.written,
+			source: src,
+		});
+		let name = String::from_iter(EscapeIterator::new(name.as_ref()));
+		writeln!(self.sink, "<{}>", name)?;
+		self.element_stack.push(name);
 
 		self.indent += 1;
+		Ok(())
 	}
 
 	pub fn start_element_attrib<'a>(
 		&mut self, name: impl AsRef<str>,
 		attributes: impl IntoIterator<Item = (impl AsRef<str> + 'a, impl AsRef<str> + 'a)>,
-	) {
-		self.indent();
-		self.data.push('<');
-		self.element_stack
-			.push(String::from_iter(EscapeIterator::new(name.as_ref())));
-		self.data.extend(EscapeIterator::new(name.as_ref()));
+	) -> io::Result<()> {
+		self.indent()?;
+		let name = String::from_iter(EscapeIterator::new(name.as_ref()));
+		write!(self.sink, "<{}", name)?;
 
 		for attribute in attributes {
-			self.data += " ";
-			self.data.extend(EscapeIterator::new(attribute.0.as_ref()));
-			self.data += "=\"";
-			self.data.extend(EscapeIterator::new(attribute.1.as_ref()));
-			self.data += "\"";
+			write!(
+				self.sink,
+				" {}=\"{}\"",
+				String::from_iter(EscapeIterator::new(attribute.0.as_ref())),
+				String::from_iter(EscapeIterator::new(attribute.1.as_ref())),
+			)?;
 		}
 
-		self.data += ">\n";
+		writeln!(self.sink, ">")?;
+		self.element_stack.push(name);
 
 		self.indent += 1;
+		Ok(())
 	}
 
 	pub fn element(
 		&mut self, name: impl AsRef<str>, attributes: impl Iterator<Item = (impl AsRef<str>, impl AsRef<str>)>,
-	) {
-		self.indent();
-		self.data.push('<');
-		self.data.extend(EscapeIterator::new(name.as_ref()));
+	) -> io::Result<()> {
+		self.indent()?;
+		write!(self.sink, "<{}", String::from_iter(EscapeIterator::new(name.as_ref())))?;
 
 		for attribute in attributes {
-			self.data += " ";
-			self.data.extend(EscapeIterator::new(attribute.0.as_ref()));
-			self.data += "=\"";
-			self.data.extend(EscapeIterator::new(attribute.1.as_ref()));
-			self.data += "\"";
+			write!(
+				self.sink,
+				" {}=\"{}\"",
+				String::from_iter(EscapeIterator::new(attribute.0.as_ref())),
+				String::from_iter(EscapeIterator::new(attribute.1.as_ref())),
+			)?;
 		}
 
-		self.data += "/>\n";
+		writeln!(self.sink, "/>")?;
+		Ok(())
 	}
 
-	pub fn data(&mut self, data: impl AsRef<str>) {
-		self.indent();
-		self.data.push_str(data.as_ref());
-		self.data.push('\n');
+	pub fn data(&mut self, data: impl AsRef<str>) -> io::Result<()> {
+		self.indent()?;
+		self.sink.write_all(data.as_ref().as_bytes())?;
+		self.sink.write_all(b"\n")?;
+		Ok(())
 	}
 
-	pub fn end_element(&mut self) {
+	/// Emits `raw` wrapped in a CDATA section so compiled RPN/script code is written
+	/// verbatim without entity escaping. Any literal `]]>` in the content is split across
+	/// two CDATA sections so the document stays well-formed.
+	pub fn cdata(&mut self, raw: &str) -> io::Result<()> {
+		self.indent()?;
+		self.sink.write_all(b"<![CDATA[")?;
+
+		let mut rest = raw;
+		while let Some(pos) = rest.find("]]>") {
+			self.sink.write_all(rest[..pos + 2].as_bytes())?;
+			self.sink.write_all(b"]]><![CDATA[")?;
+			rest = &rest[pos + 2..];
+		}
+
+		self.sink.write_all(rest.as_bytes())?;
+		self.sink.write_all(b"]]>\n")?;
+		Ok(())
+	}
+
+	pub fn end_element(&mut self) -> io::Result<()> {
 		self.indent -= 1;
-		self.indent();
+		self.indent()?;
 
-		self.data += "</";
-		self.data.push_str(&self.element_stack.pop().unwrap());
-		self.data += ">\n";
+		let name = self.element_stack.pop().unwrap();
+		writeln!(self.sink, "</{}>", name)?;
+		Ok(())
 	}
 
-	pub fn end(self) -> String { self.data + "</ModelInfo>\n" }
+	/// Writes the closing `ModelInfo` tag and returns the underlying sink.
+	pub fn end(mut self) -> io::Result<W> {
+		self.sink.write_all(b"</ModelInfo>\n")?;
+		Ok(self.sink.inner)
+	}
 
-	fn indent(&mut self) {
-		self.data.extend(IndentIterator {
-			indentation: self.indent,
-		});
+	/// Like [`end`](Self::end), but also returns the [`SourceMap`] collected from every
+	/// [`start_element_mapped`](Self::start_element_mapped) call.
+	pub fn end_mapped(mut self) -> io::Result<(W, SourceMap)> {
+		self.sink.write_all(b"</ModelInfo>\n")?;
+		Ok((self.sink.inner, SourceMap { entries: self.source_map }))
 	}
-}
 
-pub struct IndentIterator {
-	pub indentation: usize,
+	fn indent(&mut self) -> io::Result<()> {
+		for _ in 0..self.indent {
+			self.sink.write_all(b"\t")?;
+		}
+		Ok(())
+	}
 }
 
-impl Iterator for IndentIterator {
-	type Item = char;
+/// Wraps the output sink and tracks how many bytes have been written so that mapped
+/// elements can be tagged with their byte offset in the final document.
+struct Counting<W: Write> {
+	inner: W,
+	written: usize,
+}
 
-	fn next(&mut self) -> Option<Self::Item> {
-		if self.indentation > 0 {
-			self.indentation -= 1;
-			Some('\t')
-		} else {
-			None
-		}
+impl<W: Write> Write for Counting<W> {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		let written = self.inner.write(buf)?;
+		self.written += written;
+		Ok(written)
 	}
 
-	fn size_hint(&self) -> (usize, Option<usize>) { (self.indentation, Some(self.indentation)) }
+	fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
 }
 
 enum EscapeMode {
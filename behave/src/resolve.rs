@@ -1,4 +1,6 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::ops::Range;
 
 use lazy_static::lazy_static;
 
@@ -11,6 +13,8 @@ use crate::ast::{
 	EnumType,
 	FunctionAccess,
 	GlobalAccess,
+	Ident,
+	Import,
 	ImportType,
 	InbuiltEnum,
 	InbuiltFunction,
@@ -23,6 +27,7 @@ use crate::ast::{
 	Type,
 	TypeType,
 	Use,
+	variant_type_clashes,
 	AST,
 };
 use crate::diagnostic::{Diagnostic, Label, Level};
@@ -81,34 +86,243 @@ lazy_static! {
 	};
 }
 
+/// The Levenshtein edit distance between two strings, using the standard
+/// two-row dynamic-programming recurrence.
+fn edit_distance(a: &str, b: &str) -> usize {
+	let a: Vec<char> = a.chars().collect();
+	let b: Vec<char> = b.chars().collect();
+
+	let mut prev: Vec<usize> = (0..=b.len()).collect();
+	let mut curr = vec![0; b.len() + 1];
+
+	for i in 1..=a.len() {
+		curr[0] = i;
+		for j in 1..=b.len() {
+			let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+			curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+		}
+		std::mem::swap(&mut prev, &mut curr);
+	}
+
+	prev[b.len()]
+}
+
+/// The number of leading characters `a` and `b` share.
+fn common_prefix(a: &str, b: &str) -> usize { a.chars().zip(b.chars()).take_while(|(a, b)| a == b).count() }
+
+/// Owns the segments of an inbuilt map key so it can join the borrowed user-namespace
+/// keys in a single candidate stream for [`closest_candidate`].
+fn owned_segments<'c>(segments: &[&str]) -> Cow<'c, [String]> {
+	Cow::Owned(segments.iter().map(|s| s.to_string()).collect())
+}
+
+/// Finds the in-scope candidate whose final segment most closely resembles the
+/// final segment of an unresolved `path`, mirroring the "did you mean" machinery
+/// in rustc's name resolver. Candidates are only considered when their module
+/// segments match `path`'s exactly, so no cross-module suggestion is made. The
+/// closest candidate within an edit distance of `max(1, len / 3)` is returned,
+/// ties broken towards the longest shared prefix.
+fn closest_candidate<'c>(path: &[String], candidates: impl Iterator<Item = Cow<'c, [String]>>) -> Option<String> {
+	let last = path.last()?;
+	let threshold = std::cmp::max(1, last.chars().count() / 3);
+
+	let mut best: Option<(usize, String)> = None;
+	for candidate in candidates {
+		if candidate.len() != path.len() || candidate[..candidate.len() - 1] != path[..path.len() - 1] {
+			continue;
+		}
+
+		let name = candidate.last().unwrap();
+		let distance = edit_distance(last, name);
+		if distance == 0 || distance > threshold {
+			continue;
+		}
+
+		let better = match &best {
+			None => true,
+			Some((best_distance, best_name)) => {
+				distance < *best_distance
+					|| (distance == *best_distance && common_prefix(last, name) > common_prefix(last, best_name))
+			},
+		};
+		if better {
+			best = Some((distance, name.clone()));
+		}
+	}
+
+	best.map(|(_, name)| name)
+}
+
+/// A small copyable handle to an interned path, replacing the repeated
+/// `HashMap<Vec<String>, _>` lookups that otherwise allocate and hash a fresh
+/// segment vector on every resolution step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct PathId(usize);
+
+/// Deduplicates resolved paths so each distinct segment sequence is assigned a
+/// single [`PathId`], in the spirit of rust-analyzer's `intern` module. The index is
+/// keyed on a hash of the borrowed segments so a lookup probes with `&[&str]` taken
+/// straight off the path idents — no fresh `Vec<String>` is cloned per resolution step.
+/// The reverse `paths` table is retained for diagnostics, which still need the original
+/// segment strings.
+#[derive(Debug, Default)]
+struct PathInterner {
+	buckets: HashMap<u64, Vec<PathId>>,
+	paths: Vec<Vec<String>>,
+}
+
+impl PathInterner {
+	fn hash(segments: &[&str]) -> u64 {
+		use std::hash::{Hash, Hasher};
+
+		let mut hasher = std::collections::hash_map::DefaultHasher::new();
+		segments.len().hash(&mut hasher);
+		for segment in segments {
+			segment.hash(&mut hasher);
+		}
+		hasher.finish()
+	}
+
+	fn stored_eq(stored: &[String], segments: &[&str]) -> bool {
+		stored.len() == segments.len() && stored.iter().zip(segments).all(|(a, b)| a == b)
+	}
+
+	/// Interns a path, returning the existing handle if it has been seen before.
+	fn intern(&mut self, path: Vec<String>) -> PathId {
+		let segments = path.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+		if let Some(id) = self.get(&segments) {
+			return id;
+		}
+		let hash = Self::hash(&segments);
+		drop(segments);
+		let id = PathId(self.paths.len());
+		self.paths.push(path);
+		self.buckets.entry(hash).or_default().push(id);
+		id
+	}
+
+	/// The handle for an already-interned path, or `None` if it was never registered.
+	/// Probes by borrowed segments so callers reuse the idents they already hold.
+	fn get(&self, segments: &[&str]) -> Option<PathId> {
+		self.buckets
+			.get(&Self::hash(segments))?
+			.iter()
+			.copied()
+			.find(|id| Self::stored_eq(&self.paths[id.0], segments))
+	}
+
+	/// The segment strings behind an interned path, for rendering in diagnostics.
+	fn segments(&self, id: PathId) -> &[String] { &self.paths[id.0] }
+}
+
+/// Whether a resolved type is one of the compiler's inbuilt types, which a user
+/// definition of the same name is allowed to shadow.
+fn is_inbuilt_type(ty: &ResolvedType) -> bool {
+	matches!(
+		ty,
+		ResolvedType::Enum(EnumType::Inbuilt(_)) | ResolvedType::Struct(StructType::Inbuilt(_))
+	)
+}
+
+/// Warning raised when a user definition shadows an inbuilt of the same name, so
+/// the resolution is intentional rather than a silently ignored definition.
+fn shadows_inbuilt(kind: &str, name: &Ident) -> Diagnostic {
+	Diagnostic::new(Level::Warning, format!("definition shadows an inbuilt {}", kind)).add_label(Label::primary(
+		format!("this {} shadows an inbuilt of the same name", kind),
+		name.1.clone(),
+	))
+}
+
+/// A resolved import target, carrying enough to register the item under an alias
+/// in whichever of the four namespaces it belongs to.
+enum AliasItem {
+	Type(ResolvedType),
+	Template(TemplateId),
+	Function(FunctionId),
+	EnumVariant(EnumAccess),
+}
+
+/// A single glob import (`use foo::bar::*`), retained so that ambiguity between
+/// two globs can be reported against both glob sites at the point of use.
+#[derive(Debug, Clone)]
+struct Glob {
+	path: Vec<String>,
+	loc: Range<usize>,
+}
+
+impl Glob {
+	/// Renders the glob as it was written, e.g. `foo::bar::*`.
+	fn display(&self) -> String {
+		let mut s = self.path.join("::");
+		s.push_str("::*");
+		s
+	}
+}
+
 #[derive(Debug)]
 struct Resolver<'a> {
-	types: HashMap<Vec<String>, ResolvedType>,
-	templates: HashMap<Vec<String>, TemplateId>,
-	functions: HashMap<Vec<String>, FunctionId>,
-	enum_variants: HashMap<Vec<String>, EnumAccess>,
+	interner: PathInterner,
+	types: HashMap<PathId, ResolvedType>,
+	templates: HashMap<PathId, TemplateId>,
+	functions: HashMap<PathId, FunctionId>,
+	enum_variants: HashMap<PathId, EnumAccess>,
+	// Glob-imported items live under their unqualified name. A name introduced by more than one glob
+	// is ambiguous and only reported if it is actually referenced, so we keep every origin here.
+	glob_types: HashMap<String, Vec<(ResolvedType, Glob)>>,
+	glob_templates: HashMap<String, Vec<(TemplateId, Glob)>>,
+	glob_functions: HashMap<String, Vec<(FunctionId, Glob)>>,
+	glob_enum_variants: HashMap<String, Vec<(EnumAccess, Glob)>>,
+	// Every item defined anywhere in the loaded roots, keyed by its unqualified name, so an unresolved
+	// bare name can be answered with the shortest `use` path that would bring the item into scope.
+	defined_types: HashMap<String, Vec<Vec<String>>>,
+	defined_templates: HashMap<String, Vec<Vec<String>>>,
 	diagnostics: &'a mut Vec<Diagnostic>,
 }
 
 impl<'a> Resolver<'a> {
 	fn new<'b>(
 		diagnostics: &'a mut Vec<Diagnostic>, this: &'b AST<'b>, root_tree: &'b ASTTree<'b>, item_map: &'b ItemMap<'b>,
-		imports: impl Iterator<Item = &'b Path<'b>>,
+		imports: impl Iterator<Item = &'b Import<'b>>,
 	) -> Result<Resolver<'a>, ()> {
 		let mut roots = vec![root_tree];
+		let mut globs = Vec::new();
+		let mut aliases = Vec::new();
 		for import in imports {
-			match root_tree.get_ast(&import.0) {
-				Ok(tree) => roots.push(tree),
-				Err(diag) => diagnostics.push(diag.add_label(Label::primary("here", import.1.clone()))),
+			match &import.0 {
+				// An aliased import renames a single item rather than bringing its module into scope, so
+				// it is registered separately once the rest of the namespaces have been populated.
+				ImportType::Normal(path, Some(alias)) => aliases.push((path, alias)),
+				ImportType::Normal(path, None) => match root_tree.get_ast(&path.0) {
+					Ok(tree) => roots.push(tree),
+					Err(diag) => diagnostics.push(diag.add_label(Label::primary("here", path.1.clone()))),
+				},
+				ImportType::Glob(path) => match root_tree.get_ast(&path.0) {
+					Ok(tree) => globs.push((
+						tree,
+						Glob {
+							path: path.0.iter().map(|s| s.0.clone()).collect(),
+							loc: path.1.clone(),
+						},
+					)),
+					Err(diag) => diagnostics.push(diag.add_label(Label::primary("here", path.1.clone()))),
+				},
+				_ => {},
 			}
 		}
 
 		if diagnostics.len() == 0 {
 			let mut resolver = Self {
+				interner: PathInterner::default(),
 				types: HashMap::new(),
 				templates: HashMap::new(),
 				functions: HashMap::new(),
 				enum_variants: HashMap::new(),
+				glob_types: HashMap::new(),
+				glob_templates: HashMap::new(),
+				glob_functions: HashMap::new(),
+				glob_enum_variants: HashMap::new(),
+				defined_types: HashMap::new(),
+				defined_templates: HashMap::new(),
 				diagnostics,
 			};
 
@@ -119,6 +333,17 @@ impl<'a> Resolver<'a> {
 				resolver.add_items_recursive(root, item_map, &[]);
 			}
 
+			// Index the entire tree, not just the imported roots, so we can propose imports.
+			resolver.index_items(root_tree, item_map, &[]);
+
+			for (tree, glob) in globs {
+				resolver.add_glob(tree, item_map, &glob);
+			}
+
+			for (path, alias) in aliases {
+				resolver.add_alias(root_tree, item_map, path, alias);
+			}
+
 			Ok(resolver)
 		} else {
 			Err(())
@@ -127,17 +352,13 @@ impl<'a> Resolver<'a> {
 
 	fn add_inbuilt_types(&mut self) {
 		for e in INBUILT_ENUM_MAP.iter() {
-			self.types.insert(
-				e.0.into_iter().map(|f| f.to_string()).collect(),
-				ResolvedType::Enum(*e.1),
-			);
+			let id = self.interner.intern(e.0.iter().map(|f| f.to_string()).collect());
+			self.types.insert(id, ResolvedType::Enum(*e.1));
 		}
 
 		for s in INBUILT_STRUCT_MAP.iter() {
-			self.types.insert(
-				s.0.into_iter().map(|f| f.to_string()).collect(),
-				ResolvedType::Struct(*s.1),
-			);
+			let id = self.interner.intern(s.0.iter().map(|f| f.to_string()).collect());
+			self.types.insert(id, ResolvedType::Struct(*s.1));
 		}
 	}
 
@@ -146,55 +367,91 @@ impl<'a> Resolver<'a> {
 			for item in items {
 				match item.0 {
 					ItemType::Enum(e) => {
+						let module_path = path.to_vec();
 						let mut path = path.to_vec();
 						let en = item_map.get_enum(e);
 						path.push(en.name.0.clone());
-						if let Some(_) = self.types.get(&path) {
-							self.diagnostics
-								.push(
-									Diagnostic::new(Level::Error, "type redeclaration").add_label(Label::primary(
-										"a type with the same name is already in scope",
-										en.name.1.clone(),
-									)),
-								)
-						} else {
-							self.types.insert(path.clone(), ResolvedType::Enum(EnumType::User(e)));
+						let id = self.interner.intern(path.clone());
+						match self.types.get(&id) {
+							Some(ty) if !is_inbuilt_type(ty) => self.diagnostics.push(
+								Diagnostic::new(Level::Error, "type redeclaration").add_label(Label::primary(
+									"a type with the same name is already in scope",
+									en.name.1.clone(),
+								)),
+							),
+							existing => {
+								if existing.is_some() {
+									self.diagnostics.push(shadows_inbuilt("type", &en.name));
+								}
+								self.types.insert(id, ResolvedType::Enum(EnumType::User(e)));
+							},
 						}
 
 						for variant in en.variants.iter() {
 							let mut path = path.clone();
 							path.push(variant.name.0.clone());
+							if path.len() == 2
+								&& INBUILT_ENUM_ACCESS_MAP
+									.contains_key(&path.iter().map(|s| s.as_str()).collect::<Vec<_>>())
+							{
+								self.diagnostics.push(shadows_inbuilt("enum variant", &variant.name));
+							}
+							let id = self.interner.intern(path);
 							self.enum_variants.insert(
-								path,
+								id,
 								EnumAccess {
 									id: EnumType::User(e),
 									value: variant.value,
 								},
 							);
 						}
+
+						// Variant names share the type namespace, so a variant that repeats the
+						// name of a type already in scope is ambiguous when written unqualified.
+						for clash in variant_type_clashes(en, |name| {
+							let mut candidate = module_path.clone();
+							candidate.push(name.to_string());
+							self.interner
+								.get(&candidate.iter().map(|s| s.as_str()).collect::<Vec<_>>())
+								.map(|id| self.types.contains_key(&id))
+								.unwrap_or(false)
+						}) {
+							self.diagnostics.push(
+								Diagnostic::new(Level::Error, "enum variant name clashes with a type in scope")
+									.add_label(Label::primary(
+										"a type with the same name is already in scope",
+										clash.1.clone(),
+									)),
+							);
+						}
 					},
 					ItemType::Struct(s) => {
 						let mut path = path.to_vec();
 						let st = item_map.get_struct(s);
 						path.push(st.name.0.clone());
+						let id = self.interner.intern(path);
 
-						if let Some(_) = self.types.get(&path) {
-							self.diagnostics
-								.push(
-									Diagnostic::new(Level::Error, "type redeclaration").add_label(Label::primary(
-										"a type with the same name is already in scope",
-										st.name.1.clone(),
-									)),
-								)
-						} else {
-							self.types.insert(path, ResolvedType::Struct(StructType::User(s)));
+						match self.types.get(&id) {
+							Some(ty) if !is_inbuilt_type(ty) => self.diagnostics.push(
+								Diagnostic::new(Level::Error, "type redeclaration").add_label(Label::primary(
+									"a type with the same name is already in scope",
+									st.name.1.clone(),
+								)),
+							),
+							existing => {
+								if existing.is_some() {
+									self.diagnostics.push(shadows_inbuilt("type", &st.name));
+								}
+								self.types.insert(id, ResolvedType::Struct(StructType::User(s)));
+							},
 						}
 					},
 					ItemType::Template(t) => {
 						let mut path = path.to_vec();
 						let te = item_map.get_template(t);
 						path.push(te.name.0.clone());
-						if let Some(t) = self.templates.get(&path) {
+						let id = self.interner.intern(path);
+						if let Some(t) = self.templates.get(&id) {
 							self.diagnostics.push(
 								Diagnostic::new(Level::Error, "template redefinition")
 									.add_label(Label::primary(
@@ -207,14 +464,20 @@ impl<'a> Resolver<'a> {
 									)),
 							)
 						} else {
-							self.templates.insert(path, t);
+							self.templates.insert(id, t);
 						}
 					},
 					ItemType::Function(ref name, f) => {
 						let mut path = path.to_vec();
 						path.push(name.0.clone());
+						if path.len() == 1
+							&& INBUILT_FUNCTION_MAP.contains_key(&path.iter().map(|s| s.as_str()).collect::<Vec<_>>())
+						{
+							self.diagnostics.push(shadows_inbuilt("function", name));
+						}
+						let id = self.interner.intern(path);
 
-						if let Some(f) = self.functions.get(&path) {
+						if let Some(f) = self.functions.get(&id) {
 							self.diagnostics
 								.push(
 									Diagnostic::new(Level::Error, "function redefinition").add_label(Label::primary(
@@ -223,7 +486,7 @@ impl<'a> Resolver<'a> {
 									)),
 								)
 						} else {
-							self.functions.insert(path, f);
+							self.functions.insert(id, f);
 						}
 					},
 				}
@@ -243,22 +506,329 @@ impl<'a> Resolver<'a> {
 			ASTTree::Leaf(ref ast) => self.add_items(ast, item_map, path),
 		}
 	}
+
+	/// Records the full path of every item in the tree against its unqualified name,
+	/// so [`Self::suggest_import`] can later propose the shortest `use` that would bring
+	/// an unresolved bare name into scope.
+	fn index_items(&mut self, tree: &ASTTree, item_map: &ItemMap, path: &[String]) {
+		match tree {
+			ASTTree::Branch(ref map) => {
+				for pair in map {
+					let mut path = path.to_vec();
+					path.push(pair.0.clone());
+					self.index_items(pair.1, item_map, &path);
+				}
+			},
+			ASTTree::Leaf(ref ast) => {
+				if let ASTType::Secondary(ref items) = ast.ast_data {
+					for item in items {
+						let (name, index) = match item.0 {
+							ItemType::Enum(e) => (item_map.get_enum(e).name.0.clone(), &mut self.defined_types),
+							ItemType::Struct(s) => (item_map.get_struct(s).name.0.clone(), &mut self.defined_types),
+							ItemType::Template(t) => {
+								(item_map.get_template(t).name.0.clone(), &mut self.defined_templates)
+							},
+							// Functions and enum variants are resolved against the local scope, which never
+							// fails with a "does not exist", so there is nowhere to surface an import for them.
+							ItemType::Function(..) => continue,
+						};
+						let mut full = path.to_vec();
+						full.push(name.clone());
+						index.entry(name).or_default().push(full);
+					}
+				}
+			},
+		}
+	}
+
+	/// The shortest import path for a bare name, preferring the fewest module
+	/// segments and, among equal lengths, the lexicographically smallest path, in
+	/// the spirit of rust-analyzer's `find_path`.
+	fn suggest_import(candidates: Option<&Vec<Vec<String>>>) -> Option<String> {
+		candidates?
+			.iter()
+			.min_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)))
+			.map(|path| path.join("::"))
+	}
+
+	/// Resolves an aliased import (`use path::Thing as Alias`) and registers the
+	/// target item under `alias`, subjecting the alias to the same collision checks
+	/// as a locally-declared item.
+	fn add_alias(&mut self, root: &ASTTree, item_map: &ItemMap, path: &Path, alias: &Ident) {
+		if path.0.is_empty() {
+			return;
+		}
+
+		let (module, name) = path.0.split_at(path.0.len() - 1);
+		let name = &name[0].0;
+		let tree = match root.get_ast(module) {
+			Ok(tree) => tree,
+			Err(diag) => {
+				self.diagnostics.push(diag.add_label(Label::primary("here", path.1.clone())));
+				return;
+			},
+		};
+
+		if let Some(item) = Self::find_item(tree, item_map, name) {
+			self.insert_alias(item, alias, path);
+		} else {
+			self.diagnostics.push(
+				Diagnostic::new(Level::Error, "imported item does not exist")
+					.add_label(Label::primary("here", path.1.clone())),
+			);
+		}
+	}
+
+	/// Searches an imported subtree for the item named `name`, across every namespace.
+	fn find_item(tree: &ASTTree, item_map: &ItemMap, name: &str) -> Option<AliasItem> {
+		match tree {
+			ASTTree::Branch(ref map) => map.values().find_map(|tree| Self::find_item(tree, item_map, name)),
+			ASTTree::Leaf(ref ast) => {
+				if let ASTType::Secondary(ref items) = ast.ast_data {
+					for item in items {
+						match item.0 {
+							ItemType::Enum(e) => {
+								let en = item_map.get_enum(e);
+								if en.name.0 == name {
+									return Some(AliasItem::Type(ResolvedType::Enum(EnumType::User(e))));
+								}
+								for variant in en.variants.iter() {
+									if variant.name.0 == name {
+										return Some(AliasItem::EnumVariant(EnumAccess {
+											id: EnumType::User(e),
+											value: variant.value,
+										}));
+									}
+								}
+							},
+							ItemType::Struct(s) => {
+								if item_map.get_struct(s).name.0 == name {
+									return Some(AliasItem::Type(ResolvedType::Struct(StructType::User(s))));
+								}
+							},
+							ItemType::Template(t) => {
+								if item_map.get_template(t).name.0 == name {
+									return Some(AliasItem::Template(t));
+								}
+							},
+							ItemType::Function(ref n, f) => {
+								if n.0 == name {
+									return Some(AliasItem::Function(f));
+								}
+							},
+						}
+					}
+				}
+				None
+			},
+		}
+	}
+
+	/// Inserts a resolved item under its alias, emitting a redeclaration error
+	/// (pointing back at the import) if the alias is already taken in its namespace.
+	fn insert_alias(&mut self, item: AliasItem, alias: &Ident, import: &Path) {
+		let id = self.interner.intern(vec![alias.0.clone()]);
+		let clash = |kind: &str| {
+			Diagnostic::new(Level::Error, format!("{} redeclaration", kind))
+				.add_label(Label::primary(
+					format!("a {} with the same name is already in scope", kind),
+					alias.1.clone(),
+				))
+				.add_label(Label::secondary("imported here", import.1.clone()))
+		};
+
+		match item {
+			AliasItem::Type(ty) => {
+				if self.types.contains_key(&id) {
+					self.diagnostics.push(clash("type"));
+				} else {
+					self.types.insert(id, ty);
+				}
+			},
+			AliasItem::Template(t) => {
+				if self.templates.contains_key(&id) {
+					self.diagnostics.push(clash("template"));
+				} else {
+					self.templates.insert(id, t);
+				}
+			},
+			AliasItem::Function(f) => {
+				if self.functions.contains_key(&id) {
+					self.diagnostics.push(clash("function"));
+				} else {
+					self.functions.insert(id, f);
+				}
+			},
+			AliasItem::EnumVariant(e) => {
+				if self.enum_variants.contains_key(&id) {
+					self.diagnostics.push(clash("enum variant"));
+				} else {
+					self.enum_variants.insert(id, e);
+				}
+			},
+		}
+	}
+
+	/// Walks an imported subtree and brings every item it defines into scope under
+	/// its unqualified name, recording the originating glob so a later reference can
+	/// report ambiguity against both glob sites.
+	fn add_glob(&mut self, tree: &ASTTree, item_map: &ItemMap, glob: &Glob) {
+		match tree {
+			ASTTree::Branch(ref map) => {
+				for pair in map {
+					self.add_glob(pair.1, item_map, glob);
+				}
+			},
+			ASTTree::Leaf(ref ast) => {
+				if let ASTType::Secondary(ref items) = ast.ast_data {
+					for item in items {
+						match item.0 {
+							ItemType::Enum(e) => {
+								let en = item_map.get_enum(e);
+								self.glob_types
+									.entry(en.name.0.clone())
+									.or_default()
+									.push((ResolvedType::Enum(EnumType::User(e)), glob.clone()));
+
+								for variant in en.variants.iter() {
+									self.glob_enum_variants.entry(variant.name.0.clone()).or_default().push((
+										EnumAccess {
+											id: EnumType::User(e),
+											value: variant.value,
+										},
+										glob.clone(),
+									));
+								}
+							},
+							ItemType::Struct(s) => {
+								let st = item_map.get_struct(s);
+								self.glob_types
+									.entry(st.name.0.clone())
+									.or_default()
+									.push((ResolvedType::Struct(StructType::User(s)), glob.clone()));
+							},
+							ItemType::Template(t) => {
+								let te = item_map.get_template(t);
+								self.glob_templates
+									.entry(te.name.0.clone())
+									.or_default()
+									.push((t, glob.clone()));
+							},
+							ItemType::Function(ref name, f) => {
+								self.glob_functions
+									.entry(name.0.clone())
+									.or_default()
+									.push((f, glob.clone()));
+							},
+						}
+					}
+				}
+			},
+		}
+	}
+
+	/// Resolves a bare name against a glob namespace. Returns `Ok(Some(_))` for a
+	/// unique glob import, `Ok(None)` when no glob introduces the name, and `Err`
+	/// when two or more globs do — ambiguity is only surfaced here, at the use site.
+	fn resolve_glob<T: Copy>(
+		name: &str, entries: Option<&Vec<(T, Glob)>>, loc: &Range<usize>,
+	) -> Result<Option<T>, Diagnostic> {
+		match entries {
+			None => Ok(None),
+			Some(entries) if entries.len() == 1 => Ok(Some(entries[0].0)),
+			Some(entries) => {
+				let mut diagnostic = Diagnostic::new(Level::Error, format!("`{}` is ambiguous", name)).add_label(
+					Label::primary(
+						format!(
+							"imported from both `{}` and `{}`",
+							entries[0].1.display(),
+							entries[1].1.display()
+						),
+						loc.clone(),
+					),
+				);
+				for entry in entries {
+					diagnostic = diagnostic
+						.add_label(Label::secondary("glob import here", entry.1.loc.clone()));
+				}
+				Err(diagnostic)
+			},
+		}
+	}
+
+	/// Every type name a typo'd type path could have meant: user and inbuilt types in scope,
+	/// glob-imported type names, and the inbuilt enum/struct names.
+	fn type_candidates(&self) -> impl Iterator<Item = Cow<'_, [String]>> {
+		self.types
+			.keys()
+			.map(|id| Cow::Borrowed(self.interner.segments(*id)))
+			.chain(self.glob_types.keys().map(|n| Cow::Owned(vec![n.clone()])))
+			.chain(INBUILT_ENUM_MAP.keys().map(|k| owned_segments(k)))
+			.chain(INBUILT_STRUCT_MAP.keys().map(|k| owned_segments(k)))
+	}
+
+	/// Every template name a typo'd template path could have meant, including glob-imported ones.
+	fn template_candidates(&self) -> impl Iterator<Item = Cow<'_, [String]>> {
+		self.templates
+			.keys()
+			.map(|id| Cow::Borrowed(self.interner.segments(*id)))
+			.chain(self.glob_templates.keys().map(|n| Cow::Owned(vec![n.clone()])))
+	}
+
+	/// Every function or enum-variant name a typo'd access path could have meant: user items in
+	/// scope, glob-imported names, and the inbuilt functions and enum variants.
+	fn access_candidates(&self) -> impl Iterator<Item = Cow<'_, [String]>> {
+		self.functions
+			.keys()
+			.map(|id| Cow::Borrowed(self.interner.segments(*id)))
+			.chain(self.enum_variants.keys().map(|id| Cow::Borrowed(self.interner.segments(*id))))
+			.chain(self.glob_functions.keys().map(|n| Cow::Owned(vec![n.clone()])))
+			.chain(self.glob_enum_variants.keys().map(|n| Cow::Owned(vec![n.clone()])))
+			.chain(INBUILT_FUNCTION_MAP.keys().map(|k| owned_segments(k)))
+			.chain(INBUILT_ENUM_ACCESS_MAP.keys().map(|k| owned_segments(k)))
+	}
 }
 
 impl ASTPass for Resolver<'_> {
 	fn ty<'b>(&mut self, ty: &mut Type<'b>) {
 		match ty.0 {
 			TypeType::Other(ref mut user) => {
-				if let Some(resolved) = self
-					.types
-					.get(&user.path.0.iter().map(|s| s.0.clone()).collect::<Vec<_>>())
-				{
-					user.resolved = Some(*resolved);
+				let segments = user.path.0.iter().map(|s| s.0.as_str()).collect::<Vec<_>>();
+				// `types` also holds the inbuilt types, which a glob import shadows.
+				let explicit = self.interner.get(&segments).and_then(|id| self.types.get(&id)).copied();
+				let resolved = match explicit {
+					Some(resolved) if !is_inbuilt_type(&resolved) => Some(resolved),
+					_ if segments.len() == 1 => {
+						match Self::resolve_glob(segments[0], self.glob_types.get(segments[0]), &user.path.1) {
+							Ok(resolved) => resolved.or(explicit),
+							Err(diagnostic) => {
+								self.diagnostics.push(diagnostic);
+								return;
+							},
+						}
+					},
+					_ => explicit,
+				};
+
+				if let Some(resolved) = resolved {
+					user.resolved = Some(resolved);
 				} else {
-					self.diagnostics.push(
-						Diagnostic::new(Level::Error, "type does not exist")
-							.add_label(Label::primary("here", user.path.1.clone())),
-					)
+					let path = segments.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+					let suggestion = closest_candidate(&path, self.type_candidates());
+					let mut diagnostic = Diagnostic::new(Level::Error, "type does not exist")
+						.add_label(Label::primary("here", user.path.1.clone()));
+					if let Some(suggestion) = suggestion {
+						diagnostic = diagnostic.add_label(Label::secondary(
+							format!("a type with a similar name exists: `{}`", suggestion),
+							user.path.1.clone(),
+						));
+					}
+					if segments.len() == 1 {
+						if let Some(import) = Self::suggest_import(self.defined_types.get(segments[0])) {
+							diagnostic = diagnostic.add_note(format!("consider importing it: `use {};`", import));
+						}
+					}
+					self.diagnostics.push(diagnostic)
 				}
 			},
 			TypeType::Array(ref mut ty) => self.ty(ty.as_mut()),
@@ -282,42 +852,108 @@ impl ASTPass for Resolver<'_> {
 	}
 
 	fn access(&mut self, access: &mut Access) {
-		access.resolved = Some(
-			if let Some(inbuilt) =
-				INBUILT_ENUM_ACCESS_MAP.get(&access.path.0.iter().map(|s| s.0.as_str()).collect::<Vec<_>>())
-			{
-				ResolvedAccess::Global(GlobalAccess::Enum(*inbuilt))
-			} else if let Some(inbuilt) =
-				INBUILT_FUNCTION_MAP.get(&access.path.0.iter().map(|s| s.0.as_str()).collect::<Vec<_>>())
-			{
-				ResolvedAccess::Global(GlobalAccess::Function(FunctionAccess::Inbuilt(*inbuilt)))
-			} else if let Some(resolved) = self
-				.functions
-				.get(&access.path.0.iter().map(|s| s.0.clone()).collect::<Vec<_>>())
-			{
-				ResolvedAccess::Global(GlobalAccess::Function(FunctionAccess::User(*resolved)))
-			} else if let Some(resolved) = self
-				.enum_variants
-				.get(&access.path.0.iter().map(|s| s.0.clone()).collect::<Vec<_>>())
-			{
-				ResolvedAccess::Global(GlobalAccess::Enum(*resolved))
-			} else {
+		let segments = access.path.0.iter().map(|s| s.0.as_str()).collect::<Vec<_>>();
+		let id = self.interner.get(&segments);
+		// Lookup policy (shared with `ty`/`template_use`): explicit user items shadow glob imports,
+		// which shadow inbuilts. A bare name matching none of these is a local variable, so it is
+		// resolved silently; a qualified (`a::b`) name can never name a local, so a miss there is a
+		// genuine error and earns a "did you mean" suggestion like `ty`/`template_use` do.
+		let resolved = if let Some(resolved) = id.and_then(|id| self.functions.get(&id)) {
+			Some(ResolvedAccess::Global(GlobalAccess::Function(FunctionAccess::User(*resolved))))
+		} else if let Some(resolved) = id.and_then(|id| self.enum_variants.get(&id)) {
+			Some(ResolvedAccess::Global(GlobalAccess::Enum(*resolved)))
+		} else if segments.len() == 1 {
+			match Self::resolve_glob(segments[0], self.glob_functions.get(segments[0]), &access.path.1) {
+				Ok(Some(resolved)) => {
+					Some(ResolvedAccess::Global(GlobalAccess::Function(FunctionAccess::User(resolved))))
+				},
+				Ok(None) => match Self::resolve_glob(segments[0], self.glob_enum_variants.get(segments[0]), &access.path.1) {
+					Ok(Some(resolved)) => Some(ResolvedAccess::Global(GlobalAccess::Enum(resolved))),
+					Ok(None) => None,
+					Err(diagnostic) => {
+						self.diagnostics.push(diagnostic);
+						Some(ResolvedAccess::Local)
+					},
+				},
+				Err(diagnostic) => {
+					self.diagnostics.push(diagnostic);
+					Some(ResolvedAccess::Local)
+				},
+			}
+		} else {
+			None
+		};
+
+		let resolved = resolved
+			.or_else(|| {
+				INBUILT_ENUM_ACCESS_MAP
+					.get(&segments)
+					.map(|inbuilt| ResolvedAccess::Global(GlobalAccess::Enum(*inbuilt)))
+			})
+			.or_else(|| {
+				INBUILT_FUNCTION_MAP
+					.get(&segments)
+					.map(|inbuilt| ResolvedAccess::Global(GlobalAccess::Function(FunctionAccess::Inbuilt(*inbuilt))))
+			});
+
+		access.resolved = Some(match resolved {
+			Some(resolved) => resolved,
+			None => {
+				if segments.len() > 1 {
+					let path = segments.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+					let suggestion = closest_candidate(&path, self.access_candidates());
+					let mut diagnostic = Diagnostic::new(Level::Error, "item does not exist")
+						.add_label(Label::primary("here", access.path.1.clone()));
+					if let Some(suggestion) = suggestion {
+						diagnostic = diagnostic.add_label(Label::secondary(
+							format!("an item with a similar name exists: `{}`", suggestion),
+							access.path.1.clone(),
+						));
+					}
+					self.diagnostics.push(diagnostic);
+				}
 				ResolvedAccess::Local
 			},
-		);
+		});
 	}
 
 	fn template_use<'b>(&mut self, us: &mut Use<'b>) {
-		if let Some(resolved) = self
-			.templates
-			.get(&us.template.path.0.iter().map(|s| s.0.clone()).collect::<Vec<_>>())
-		{
-			us.template.resolved = Some(*resolved);
+		let segments = us.template.path.0.iter().map(|s| s.0.as_str()).collect::<Vec<_>>();
+		let mut reported = false;
+		let resolved = if let Some(resolved) = self.interner.get(&segments).and_then(|id| self.templates.get(&id)) {
+			Some(*resolved)
+		} else if segments.len() == 1 {
+			match Self::resolve_glob(segments[0], self.glob_templates.get(segments[0]), &us.template.path.1) {
+				Ok(resolved) => resolved,
+				Err(diagnostic) => {
+					self.diagnostics.push(diagnostic);
+					reported = true;
+					None
+				},
+			}
 		} else {
-			self.diagnostics.push(
-				Diagnostic::new(Level::Error, "template does not exist")
-					.add_label(Label::primary("here", us.template.path.1.clone())),
-			)
+			None
+		};
+
+		if let Some(resolved) = resolved {
+			us.template.resolved = Some(resolved);
+		} else if !reported {
+			let path = segments.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+			let suggestion = closest_candidate(&path, self.template_candidates());
+			let mut diagnostic = Diagnostic::new(Level::Error, "template does not exist")
+				.add_label(Label::primary("here", us.template.path.1.clone()));
+			if let Some(suggestion) = suggestion {
+				diagnostic = diagnostic.add_label(Label::secondary(
+					format!("a template with a similar name exists: `{}`", suggestion),
+					us.template.path.1.clone(),
+				));
+			}
+			if segments.len() == 1 {
+				if let Some(import) = Self::suggest_import(self.defined_templates.get(segments[0])) {
+					diagnostic = diagnostic.add_note(format!("consider importing it: `use {};`", import));
+				}
+			}
+			self.diagnostics.push(diagnostic)
 		}
 
 		for arg in us.args.iter_mut() {
@@ -334,14 +970,7 @@ pub fn resolve(main: &mut AST, others: &mut ASTTree, item_map: &mut ItemMap) ->
 		errors.extend(diag);
 	}
 
-	let imports = main.imports.iter().filter_map(|import| {
-		if let ImportType::Normal(p) = &import.0 {
-			Some(p)
-		} else {
-			None
-		}
-	});
-	let mut resolver = if let Ok(res) = Resolver::new(&mut errors, main, others, item_map, imports) {
+	let mut resolver = if let Ok(res) = Resolver::new(&mut errors, main, others, item_map, main.imports.iter()) {
 		res
 	} else {
 		return Err(errors);
@@ -373,19 +1002,7 @@ fn resolve_imported(root: &ASTTree, tree: &mut ASTTree, item_map: &mut ItemMap)
 			}
 		},
 		ASTTree::Leaf(ref mut ast) => {
-			let mut resolver = if let Ok(res) = Resolver::new(
-				&mut errors,
-				ast,
-				root,
-				item_map,
-				ast.imports.iter().filter_map(|import| {
-					if let ImportType::Normal(p) = &import.0 {
-						Some(p)
-					} else {
-						None
-					}
-				}),
-			) {
+			let mut resolver = if let Ok(res) = Resolver::new(&mut errors, ast, root, item_map, ast.imports.iter()) {
 				res
 			} else {
 				return Err(errors);